@@ -0,0 +1,70 @@
+//! Benchmarks of a mostly-`Ok` workload, where the errors are rare, comparing
+//! `skip_error()` with `flatten()`, and `skip_error!` with a manual `match` in
+//! a loop.
+//!
+//! The error paths of the crate are marked `#[cold]`, except with `--cfg
+//! skip_error_no_cold`, to compare them with a baseline without the hints:
+//!
+//! ```sh
+//! RUSTFLAGS="--cfg skip_error_no_cold" cargo bench --bench skip_error -- --save-baseline no-cold
+//! cargo bench --bench skip_error -- --baseline no-cold
+//! ```
+#![allow(deprecated)]
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use skip_error::{skip_error, SkipError};
+
+// One error every thousand values
+fn results() -> Vec<Result<u64, &'static str>> {
+    (0..100_000u64)
+        .map(|n| if n % 1000 == 999 { Err("boom") } else { Ok(n) })
+        .collect()
+}
+
+fn adapter(c: &mut Criterion) {
+    let results = results();
+    let mut group = c.benchmark_group("adapter");
+    group.bench_function("flatten", |b| {
+        b.iter(|| black_box(&results).iter().copied().flatten().sum::<u64>())
+    });
+    group.bench_function("skip_error", |b| {
+        b.iter(|| {
+            black_box(&results)
+                .iter()
+                .copied()
+                .skip_error()
+                .sum::<u64>()
+        })
+    });
+    group.finish();
+}
+
+fn macros(c: &mut Criterion) {
+    let results = results();
+    let mut group = c.benchmark_group("macro");
+    group.bench_function("match", |b| {
+        b.iter(|| {
+            let mut sum = 0u64;
+            for result in black_box(&results) {
+                sum += match result {
+                    Ok(value) => value,
+                    Err(_) => continue,
+                };
+            }
+            sum
+        })
+    });
+    group.bench_function("skip_error", |b| {
+        b.iter(|| {
+            let mut sum = 0u64;
+            for result in black_box(&results) {
+                sum += skip_error!(result.as_ref());
+            }
+            sum
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, adapter, macros);
+criterion_main!(benches);
@@ -0,0 +1,109 @@
+//! Derive macro of `skip_error::SkippableError`, see the documentation of
+//! [`skip_error`](https://docs.rs/skip_error) with the feature `derive`.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Error, LitStr};
+
+/// Implements `skip_error::SkippableError`: the variants with an attribute
+/// `#[skip]` are skippable and logged at the level `warn`, or at the level
+/// given with `#[skip(level = "debug")]`, or not logged with
+/// `#[skip(silent)]`. The other variants are fatal.
+///
+/// On a `struct`, the attribute applies to the whole type.
+#[proc_macro_derive(SkippableError, attributes(skip))]
+pub fn derive_skippable_error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+// How a variant is skipped, from its attribute `#[skip]`
+enum Skip {
+    Fatal,
+    Silent,
+    Level(TokenStream2),
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let arms = match &input.data {
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let name = &variant.ident;
+                Ok((quote!(Self::#name { .. }), skip(&variant.attrs)?))
+            })
+            .collect::<syn::Result<Vec<_>>>()?,
+        Data::Struct(_) => vec![(quote!(_), skip(&input.attrs)?)],
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`SkippableError` cannot be derived for a union",
+            ))
+        }
+    };
+    let is_skippable = arms.iter().map(|(pattern, skip)| {
+        let skippable = !matches!(skip, Skip::Fatal);
+        quote!(#pattern => #skippable,)
+    });
+    let skip_level = arms.iter().map(|(pattern, skip)| match skip {
+        Skip::Level(level) => quote!(#pattern => ::core::option::Option::Some(#level),),
+        Skip::Fatal | Skip::Silent => quote!(#pattern => ::core::option::Option::None,),
+    });
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::skip_error::SkippableError for #name #type_generics #where_clause {
+            fn is_skippable(&self) -> bool {
+                match *self {
+                    #(#is_skippable)*
+                }
+            }
+
+            fn skip_level(&self) -> ::core::option::Option<::skip_error::SkipLevel> {
+                match *self {
+                    #(#skip_level)*
+                }
+            }
+        }
+    })
+}
+
+fn skip(attrs: &[Attribute]) -> syn::Result<Skip> {
+    let Some(attr) = attrs.iter().find(|attr| attr.path().is_ident("skip")) else {
+        return Ok(Skip::Fatal);
+    };
+    let mut skip = Skip::Level(quote!(::skip_error::SkipLevel::Warn));
+    // A bare `#[skip]` has no arguments to parse
+    if matches!(attr.meta, syn::Meta::Path(_)) {
+        return Ok(skip);
+    }
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("silent") {
+            skip = Skip::Silent;
+            return Ok(());
+        }
+        if meta.path.is_ident("level") {
+            let level: LitStr = meta.value()?.parse()?;
+            let variant = match level.value().as_str() {
+                "error" => quote!(Error),
+                "warn" => quote!(Warn),
+                "info" => quote!(Info),
+                "debug" => quote!(Debug),
+                "trace" => quote!(Trace),
+                _ => {
+                    return Err(Error::new_spanned(
+                        level,
+                        "expected one of \"error\", \"warn\", \"info\", \"debug\" or \"trace\"",
+                    ))
+                }
+            };
+            skip = Skip::Level(quote!(::skip_error::SkipLevel::#variant));
+            return Ok(());
+        }
+        Err(meta.error("expected `level = \"...\"` or `silent`"))
+    })?;
+    Ok(skip)
+}
@@ -0,0 +1,66 @@
+//! Logging of the skipped errors from a dedicated thread.
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+
+// A bounded queue of messages, logged in order by a background thread
+pub(crate) struct BackgroundLog {
+    sender: Option<SyncSender<(String, crate::Level)>>,
+    thread: Option<JoinHandle<()>>,
+    // The messages not logged since the queue was full, with the level of
+    // the last one
+    dropped: Option<(usize, crate::Level)>,
+}
+
+impl BackgroundLog {
+    // Returns `None` if the thread can't be spawned, so the messages are
+    // logged inline
+    pub(crate) fn spawn(capacity: usize) -> Option<Self> {
+        let (sender, receiver) = mpsc::sync_channel::<(String, crate::Level)>(capacity);
+        let thread = thread::Builder::new()
+            .name("skip_error".to_string())
+            .spawn(move || {
+                for (message, log_level) in receiver {
+                    crate::__log!(message, log_level);
+                }
+            })
+            .ok()?;
+        Some(BackgroundLog {
+            sender: Some(sender),
+            thread: Some(thread),
+            dropped: None,
+        })
+    }
+
+    pub(crate) fn send(&mut self, message: String, log_level: crate::Level) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        match sender.try_send((message, log_level)) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                let dropped = self.dropped.map_or(0, |(dropped, _)| dropped);
+                self.dropped = Some((dropped + 1, log_level));
+            }
+            // The thread is gone, for example after a panic of the logger
+            Err(TrySendError::Disconnected((message, log_level))) => {
+                crate::__log!(message, log_level);
+            }
+        }
+    }
+}
+
+// The queued messages are all logged when the adapter is dropped
+impl Drop for BackgroundLog {
+    fn drop(&mut self) {
+        if let Some(sender) = self.sender.take() {
+            if let Some((dropped, log_level)) = self.dropped {
+                let message = format!("{} skipped errors not logged, the queue was full", dropped);
+                // Blocks until there is room, since this is the last message
+                let _ = sender.send((message, log_level));
+            }
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
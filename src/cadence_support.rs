@@ -0,0 +1,44 @@
+//! Counting the skipped errors with a statsd client of
+//! [`cadence`](::cadence).
+use cadence::{CountedExt, StatsdClient};
+use std::sync::RwLock;
+
+static COUNTER: RwLock<Option<(StatsdClient, String)>> = RwLock::new(None);
+
+/// Sets the statsd `client` whose counter `key` is incremented by the macros
+/// and the adapters for every skipped error, replacing the previous one.
+///
+/// The prefix of the metrics is the one of the client.
+///
+/// ```edition2018
+/// use cadence::{SpyMetricSink, StatsdClient};
+/// use skip_error::SkipError;
+/// let (metrics, sink) = SpyMetricSink::new();
+/// skip_error::set_statsd_client(StatsdClient::from_sink("import", sink), "rejected");
+/// let numbers: Vec<u32> = ["1", "two", "3", "four"]
+///   .iter()
+///   .map(|string_number| string_number.parse())
+///   .skip_error()
+///   .collect();
+/// let metrics: Vec<Vec<u8>> = metrics.try_iter().collect();
+/// assert_eq!(metrics, vec![b"import.rejected:1|c".to_vec(), b"import.rejected:1|c".to_vec()]);
+/// ```
+pub fn set_statsd_client<K>(client: StatsdClient, key: K)
+where
+    K: Into<String>,
+{
+    let mut counter = COUNTER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *counter = Some((client, key.into()));
+}
+
+pub(crate) fn record() {
+    let counter = COUNTER
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((client, key)) = counter.as_ref() {
+        // The skipped error must not fail because of the metrics
+        let _ = client.incr(key);
+    }
+}
@@ -0,0 +1,91 @@
+//! Skipping the errors received from the [`std::sync::mpsc`] channels.
+use crate::SkipError;
+use std::sync::mpsc::Receiver;
+
+/// Trait to extend the receivers of the [`std::sync::mpsc`] channels carrying
+/// [`Result`].
+///
+/// The receivers are [`IntoIterator`], so all the errors until the channel is
+/// closed are skipped with [`SkipError`].
+///
+/// ```edition2018
+/// use skip_error::{SkipError, SkipErrorStdReceiverExt};
+/// let (sender, receiver) = std::sync::mpsc::channel();
+/// std::thread::spawn(move || {
+///   for string_number in &["1", "2", "three", "4"] {
+///     sender.send(string_number.parse::<u32>()).unwrap();
+///   }
+/// });
+/// assert_eq!(receiver.recv_ok(), Some(1));
+/// let numbers: Vec<u32> = receiver.skip_error().collect();
+/// assert_eq!(numbers, vec![2, 4]);
+/// ```
+pub trait SkipErrorStdReceiverExt<T, E>: Sized {
+    /// Blocks until an `Ok()` value is received, skipping the errors, or
+    /// returns `None` if the channel is closed.
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `recv_ok_and_log()`"
+        )
+    )]
+    fn recv_ok(&self) -> Option<T>
+    where
+        E: std::fmt::Display;
+
+    /// Blocks until an `Ok()` value is received, skipping and logging the
+    /// errors, or returns `None` if the channel is closed.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipErrorStdReceiverExt;
+    /// # testing_logger::setup();
+    /// let (sender, receiver) = std::sync::mpsc::channel();
+    /// sender.send("three".parse::<u32>()).unwrap();
+    /// sender.send("4".parse::<u32>()).unwrap();
+    /// drop(sender);
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "assert_eq!(receiver.recv_ok_and_log(log::Level::Warn), Some(4));"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "assert_eq!(receiver.recv_ok_and_log(tracing::Level::WARN), Some(4));"
+    )]
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "assert_eq!(receiver.recv_ok_and_log(log::Level::Warn), None);"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "assert_eq!(receiver.recv_ok_and_log(tracing::Level::WARN), None);"
+    )]
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs.len(), 1);
+    ///   assert_eq!(captured_logs[0].body, "invalid digit found in string");
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn recv_ok_and_log<L>(&self, log_level: L) -> Option<T>
+    where
+        L: Into<crate::Level>,
+        E: std::fmt::Display;
+}
+
+impl<T, E> SkipErrorStdReceiverExt<T, E> for Receiver<Result<T, E>> {
+    #[allow(deprecated)]
+    fn recv_ok(&self) -> Option<T>
+    where
+        E: std::fmt::Display,
+    {
+        self.iter().skip_error().next()
+    }
+
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn recv_ok_and_log<L>(&self, log_level: L) -> Option<T>
+    where
+        L: Into<crate::Level>,
+        E: std::fmt::Display,
+    {
+        self.iter().skip_error_and_log(log_level.into()).next()
+    }
+}
@@ -0,0 +1,54 @@
+//! Choosing the level of the logs from the verbosity flags of
+//! [`clap-verbosity-flag`](::clap_verbosity_flag).
+use clap_verbosity_flag::{LogLevel, Verbosity, VerbosityFilter};
+
+// The same level for all the backends
+macro_rules! level {
+    ($level:ident, $tracing_level:ident) => {{
+        #[cfg(all(feature = "log", not(feature = "tracing")))]
+        let level = log::Level::$level;
+        #[cfg(feature = "tracing")]
+        let level = tracing::Level::$tracing_level;
+        #[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+        let level = crate::DefmtLevel::$level;
+        level
+    }};
+}
+
+/// Returns the most verbose level enabled by the `-v`/`-q` flags of a
+/// command line, so the skipped errors logged at this level are shown.
+///
+/// If all the logs are disabled, the level of the errors is returned: the
+/// logger is expected to filter it out too.
+///
+/// ```edition2018
+/// # #[macro_use]
+/// # extern crate skip_error;
+/// use clap_verbosity_flag::{Verbosity, WarnLevel};
+/// # fn main() {
+/// # testing_logger::setup();
+/// // As parsed from `-v`
+/// let verbosity = Verbosity::<WarnLevel>::new(1, 0);
+/// for string_number in &["1", "2", "three", "4"] {
+///   let number: u32 = skip_error_and_log!(
+///     string_number.parse(),
+///     skip_error::verbosity_level(&verbosity)
+///   );
+/// }
+/// testing_logger::validate(|captured_logs| {
+///   assert_eq!(captured_logs[0].level, log::Level::Info);
+/// });
+/// # }
+/// ```
+pub fn verbosity_level<L>(verbosity: &Verbosity<L>) -> crate::Level
+where
+    L: LogLevel,
+{
+    match verbosity.filter() {
+        VerbosityFilter::Off | VerbosityFilter::Error => level!(Error, ERROR),
+        VerbosityFilter::Warn => level!(Warn, WARN),
+        VerbosityFilter::Info => level!(Info, INFO),
+        VerbosityFilter::Debug => level!(Debug, DEBUG),
+        VerbosityFilter::Trace => level!(Trace, TRACE),
+    }
+}
@@ -0,0 +1,173 @@
+//! Classification of the errors into skippable and fatal ones.
+use crate::Skipper;
+
+/// The level at which a [`SkippableError`] is logged when it is skipped,
+/// whatever the logging backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SkipLevel {
+    /// The level of the errors.
+    Error,
+    /// The level of the warnings.
+    Warn,
+    /// The level of the informations.
+    Info,
+    /// The level of the debugging informations.
+    Debug,
+    /// The level of the traces.
+    Trace,
+}
+
+#[cfg(feature = "log")]
+impl From<SkipLevel> for log::Level {
+    fn from(level: SkipLevel) -> Self {
+        match level {
+            SkipLevel::Error => log::Level::Error,
+            SkipLevel::Warn => log::Level::Warn,
+            SkipLevel::Info => log::Level::Info,
+            SkipLevel::Debug => log::Level::Debug,
+            SkipLevel::Trace => log::Level::Trace,
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl From<SkipLevel> for tracing::Level {
+    fn from(level: SkipLevel) -> Self {
+        match level {
+            SkipLevel::Error => tracing::Level::ERROR,
+            SkipLevel::Warn => tracing::Level::WARN,
+            SkipLevel::Info => tracing::Level::INFO,
+            SkipLevel::Debug => tracing::Level::DEBUG,
+            SkipLevel::Trace => tracing::Level::TRACE,
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl From<SkipLevel> for crate::DefmtLevel {
+    fn from(level: SkipLevel) -> Self {
+        match level {
+            SkipLevel::Error => crate::DefmtLevel::Error,
+            SkipLevel::Warn => crate::DefmtLevel::Warn,
+            SkipLevel::Info => crate::DefmtLevel::Info,
+            SkipLevel::Debug => crate::DefmtLevel::Debug,
+            SkipLevel::Trace => crate::DefmtLevel::Trace,
+        }
+    }
+}
+
+/// An error which knows whether it can be skipped, or else is fatal, and at
+/// which level it is logged when skipped.
+///
+/// It is used by [`SkipError::skip_skippable()`](crate::SkipError::skip_skippable)
+/// and by [`skip_skippable!`](crate::skip_skippable), so the policy is
+/// written once with the error rather than at each place it is skipped.
+#[cfg_attr(
+    feature = "derive",
+    doc = r##"
+With the feature `derive`, it can be derived: the variants with an attribute
+`#[skip]` are skippable and logged at the level `warn`, or at the level given
+with `#[skip(level = "debug")]`, or not logged with `#[skip(silent)]`. The
+other variants are fatal.
+```edition2018
+use skip_error::{SkipLevel, SkippableError};
+
+#[derive(SkippableError)]
+enum ImportError {
+  #[skip(level = "debug")]
+  EmptyLine,
+  #[skip]
+  InvalidStop(String),
+  #[skip(silent)]
+  Comment { line: usize },
+  Disconnected,
+}
+
+assert_eq!(ImportError::EmptyLine.skip_level(), Some(SkipLevel::Debug));
+assert_eq!(ImportError::InvalidStop("A".to_string()).skip_level(), Some(SkipLevel::Warn));
+assert!(ImportError::Comment { line: 3 }.is_skippable());
+assert_eq!(ImportError::Comment { line: 3 }.skip_level(), None);
+assert!(!ImportError::Disconnected.is_skippable());
+```
+"##
+)]
+pub trait SkippableError {
+    /// Whether the error can be skipped, or else must stop the processing.
+    fn is_skippable(&self) -> bool;
+
+    /// The level at which the error is logged when it is skipped, or `None`
+    /// to skip it silently.
+    fn skip_level(&self) -> Option<SkipLevel>;
+}
+
+/// An iterator skipping the [`SkippableError`]s, logged at their own level,
+/// and yielding the fatal ones.
+///
+/// This `struct` is created by [`SkipError::skip_skippable()`](crate::SkipError::skip_skippable).
+pub struct SkipSkippable<I> {
+    inner: I,
+    skipper: Skipper,
+}
+
+impl<I> SkipSkippable<I> {
+    skip_adapter_methods!();
+    budget_adapter_methods!();
+
+    pub(crate) fn new(inner: I) -> Self {
+        SkipSkippable {
+            inner,
+            skipper: Skipper::new(),
+        }
+    }
+}
+
+impl<I, T, E> Iterator for SkipSkippable<I>
+where
+    I: Iterator<Item = Result<T, E>>,
+    E: SkippableError + core::fmt::Display,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.skipper.exhausted() {
+            match self.inner.next()? {
+                Ok(value) => {
+                    self.skipper.ok();
+                    return Some(Ok(value));
+                }
+                Err(error) if error.is_skippable() => {
+                    // Each error is logged at its own level
+                    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+                    let log_level = core::mem::replace(
+                        &mut self.skipper.log_level,
+                        error.skip_level().map(Into::into),
+                    );
+                    self.skipper.skip(&error);
+                    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+                    {
+                        self.skipper.log_level = log_level;
+                    }
+                }
+                Err(error) => return Some(Err(error)),
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+// Logs a skipped error at its own level, for `skip_skippable!`
+#[doc(hidden)]
+pub fn __log_skippable<E>(_error: &E)
+where
+    E: SkippableError + core::fmt::Display,
+{
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    if let Some(log_level) = _error.skip_level() {
+        let error = crate::__formatted(_error, None);
+        crate::__log!(error, crate::Level::from(log_level));
+    }
+}
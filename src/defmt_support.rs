@@ -0,0 +1,35 @@
+//! Emit the skipped errors with [`defmt`](https://docs.rs/defmt).
+#[doc(hidden)]
+pub use defmt;
+
+/// The level of the `defmt` messages emitted for the skipped errors.
+///
+/// It is used instead of `log::Level` or `tracing::Level` when the `defmt`
+/// feature is enabled without `log` and `tracing`.
+#[cfg_attr(
+    not(any(feature = "log", feature = "tracing")),
+    doc = r##"
+```edition2018,no_run
+# #[macro_use]
+# extern crate skip_error;
+# fn main() {
+for string_number in &["1", "2", "three", "4"] {
+  let number: u32 = skip_error_and_log!(string_number.parse(), skip_error::DefmtLevel::Warn);
+}
+# }
+```
+"##
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DefmtLevel {
+    /// Emitted with `defmt::error!`.
+    Error,
+    /// Emitted with `defmt::warn!`.
+    Warn,
+    /// Emitted with `defmt::info!`.
+    Info,
+    /// Emitted with `defmt::debug!`.
+    Debug,
+    /// Emitted with `defmt::trace!`.
+    Trace,
+}
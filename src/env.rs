@@ -0,0 +1,147 @@
+//! Loading typed values from the environment variables, skipping the missing
+//! or invalid ones.
+use crate::skipper::Skipper;
+use std::{
+    collections::HashMap,
+    ffi::{OsStr, OsString},
+    str::FromStr,
+};
+
+/// Returns the environment variables of the process, to parse the selected
+/// ones with [`EnvVars::var()`], skipping the variables which are missing or
+/// malformed instead of failing.
+///
+/// ```edition2018
+/// std::env::set_var("SKIP_ERROR_VARS_PORT", "8080");
+/// std::env::set_var("SKIP_ERROR_VARS_WORKERS", "many");
+/// let mut vars = skip_error::env::vars_skipping();
+/// let port: Option<u16> = vars.var("SKIP_ERROR_VARS_PORT");
+/// let workers: usize = vars.var_or("SKIP_ERROR_VARS_WORKERS", 4);
+/// let timeout: Option<u64> = vars.var("SKIP_ERROR_VARS_TIMEOUT");
+/// assert_eq!((port, workers, timeout), (Some(8080), 4, None));
+/// assert_eq!(vars.report().ok(), 1);
+/// assert_eq!(vars.report().skipped(), 2);
+/// ```
+#[cfg_attr(
+    feature = "forbid-silent",
+    deprecated(
+        note = "the errors must not be skipped silently, log them with `vars_skipping_and_log()`"
+    )
+)]
+pub fn vars_skipping() -> EnvVars {
+    EnvVars::new(Skipper::new())
+}
+
+/// Returns the environment variables of the process, logging the variables
+/// which are skipped (see [`vars_skipping()`]).
+///
+/// ```edition2018
+/// # testing_logger::setup();
+/// std::env::set_var("SKIP_ERROR_VARS_AND_LOG_PORT", "http");
+/// let mut vars = skip_error::env::vars_skipping_and_log(
+#[cfg_attr(
+    all(feature = "log", not(feature = "tracing")),
+    doc = "  log::Level::Warn,"
+)]
+#[cfg_attr(feature = "tracing", doc = "  tracing::Level::WARN,")]
+/// );
+/// let port: u16 = vars.var_or("SKIP_ERROR_VARS_AND_LOG_PORT", 80);
+/// assert_eq!(port, 80);
+/// testing_logger::validate(|captured_logs| {
+///   assert_eq!(
+///     captured_logs[0].body,
+///     "invalid environment variable SKIP_ERROR_VARS_AND_LOG_PORT: the value could not be parsed"
+///   );
+/// });
+/// ```
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+pub fn vars_skipping_and_log<L>(log_level: L) -> EnvVars
+where
+    L: Into<crate::Level>,
+{
+    EnvVars::new(Skipper::with_log_level(log_level.into()))
+}
+
+/// The environment variables of the process, read when it is created.
+///
+/// Each variable parsed is counted as an `Ok()` value in the
+/// [`SkipReport`](crate::SkipReport), and each variable which is missing, is
+/// not valid unicode or could not be parsed is counted as a skipped error.
+/// The values are never logged, only the names of the variables: the parse
+/// errors are not logged either, since their message often contains the value,
+/// which may be a secret.
+///
+/// This `struct` is created by [`vars_skipping()`].
+pub struct EnvVars {
+    vars: HashMap<OsString, OsString>,
+    skipper: Skipper,
+}
+
+impl EnvVars {
+    skip_adapter_methods!();
+
+    fn new(skipper: Skipper) -> Self {
+        EnvVars {
+            vars: std::env::vars_os().collect(),
+            skipper,
+        }
+    }
+
+    /// Parses the variable `name` with [`FromStr`], or returns `None` if it
+    /// is skipped.
+    pub fn var<T>(&mut self, name: &str) -> Option<T>
+    where
+        T: FromStr,
+    {
+        let parsed = match self.vars.get(OsStr::new(name)) {
+            None => Err(EnvVarError::Missing(name)),
+            Some(value) => match value.to_str() {
+                None => Err(EnvVarError::NotUnicode(name)),
+                Some(value) => value.parse().map_err(|_| EnvVarError::Invalid(name)),
+            },
+        };
+        match parsed {
+            Ok(value) => {
+                self.skipper.ok();
+                Some(value)
+            }
+            Err(error) => {
+                self.skipper.skip(&error);
+                None
+            }
+        }
+    }
+
+    /// Parses the variable `name` with [`FromStr`], or returns `default` if
+    /// it is skipped.
+    pub fn var_or<T>(&mut self, name: &str, default: T) -> T
+    where
+        T: FromStr,
+    {
+        self.var(name).unwrap_or(default)
+    }
+}
+
+// Why an environment variable was skipped, without its value (nor the parse
+// error, which usually contains it) which may be a secret
+enum EnvVarError<'a> {
+    Missing(&'a str),
+    NotUnicode(&'a str),
+    Invalid(&'a str),
+}
+
+impl std::fmt::Display for EnvVarError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnvVarError::Missing(name) => write!(f, "environment variable {} is not set", name),
+            EnvVarError::NotUnicode(name) => {
+                write!(f, "environment variable {} is not valid unicode", name)
+            }
+            EnvVarError::Invalid(name) => write!(
+                f,
+                "invalid environment variable {}: the value could not be parsed",
+                name
+            ),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Rendering of the [`eyre::Report`] errors with their report handler.
+use std::fmt;
+
+/// An [`eyre::Report`] displayed with the rich rendering of its report
+/// handler, with the chain of causes and whatever the handler adds (like the
+/// colors and the span traces of `color-eyre`), instead of its flat
+/// [`std::fmt::Display`].
+///
+/// The errors skipped by the macros and the adapters are rendered with
+/// [`std::fmt::Display`], so convert the errors into [`EyreReport`] to keep
+/// their diagnostics.
+///
+/// ```edition2018
+/// use eyre::WrapErr;
+/// use skip_error::{EyreReport, SkipError};
+/// # testing_logger::setup();
+/// let numbers: Vec<u32> = ["1", "two", "3"]
+///   .iter()
+///   .map(|string_number| {
+///     string_number
+///       .parse()
+///       .wrap_err_with(|| format!("invalid line {:?}", string_number))
+///       .map_err(EyreReport::from)
+///   })
+#[cfg_attr(
+    all(feature = "log", not(feature = "tracing")),
+    doc = "  .skip_error_and_log(log::Level::Warn)"
+)]
+#[cfg_attr(
+    feature = "tracing",
+    doc = "  .skip_error_and_log(tracing::Level::WARN)"
+)]
+#[cfg_attr(
+    not(any(feature = "log", feature = "tracing")),
+    doc = "  .skip_error()"
+)]
+///   .collect();
+/// assert_eq!(numbers, vec![1, 3]);
+#[cfg_attr(
+    any(feature = "log", feature = "tracing"),
+    doc = r##"
+testing_logger::validate(|captured_logs| {
+  assert!(captured_logs[0].body.starts_with("invalid line \"two\""));
+  assert!(captured_logs[0].body.contains("invalid digit found in string"));
+});
+"##
+)]
+/// ```
+pub struct EyreReport(pub eyre::Report);
+
+impl From<eyre::Report> for EyreReport {
+    fn from(report: eyre::Report) -> Self {
+        EyreReport(report)
+    }
+}
+
+impl fmt::Debug for EyreReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for EyreReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
@@ -0,0 +1,65 @@
+//! Skipping the errors of the iterators of
+//! [`fallible-iterator`](https://docs.rs/fallible-iterator).
+use crate::{SkipError, SkipErrorIter};
+use fallible_iterator::FallibleIterator;
+
+/// Trait to extend any [`FallibleIterator`] with the adapters of
+/// [`SkipError`], turning it into a plain [`Iterator`] of its values.
+pub trait FallibleSkipError: FallibleIterator + Sized {
+    /// Skip all errors of the original [`FallibleIterator`].
+    ///
+    /// ```edition2018
+    /// use skip_error::FallibleSkipError;
+    /// let records = fallible_iterator::convert(vec![Ok(1), Err("truncated record"), Ok(3)].into_iter());
+    /// let numbers: Vec<u32> = records.skip_error().collect();
+    /// assert_eq!(numbers, vec![1, 3]);
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `skip_error_and_log()`"
+        )
+    )]
+    #[allow(deprecated)]
+    fn skip_error(
+        self,
+    ) -> SkipErrorIter<fallible_iterator::Iterator<Self>, Self::Item, Self::Error> {
+        self.iterator().skip_error()
+    }
+
+    /// Skip all errors of the original [`FallibleIterator`].  This also allows
+    /// to log the errors, choosing which level to use (see
+    /// [`SkipError::skip_error_and_log()`]).
+    ///
+    /// ```edition2018
+    /// use skip_error::FallibleSkipError;
+    /// # testing_logger::setup();
+    /// let records = fallible_iterator::convert(vec![Ok(1), Err("truncated record"), Ok(3)].into_iter());
+    /// let numbers: Vec<u32> = records
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .skip_error_and_log(log::Level::Warn)"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .skip_error_and_log(tracing::Level::WARN)"
+    )]
+    ///   .collect();
+    /// assert_eq!(numbers, vec![1, 3]);
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs[0].body, "truncated record");
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn skip_error_and_log<L>(
+        self,
+        log_level: L,
+    ) -> SkipErrorIter<fallible_iterator::Iterator<Self>, Self::Item, Self::Error>
+    where
+        L: Into<crate::Level>,
+    {
+        self.iterator().skip_error_and_log(log_level.into())
+    }
+}
+
+impl<I> FallibleSkipError for I where I: FallibleIterator {}
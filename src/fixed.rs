@@ -0,0 +1,167 @@
+//! Collection of the skipped errors into fixed-capacity buffers, without
+//! allocation.
+use crate::SkipReport;
+use core::mem::MaybeUninit;
+
+/// A fixed-capacity buffer keeping the skipped errors.
+///
+/// It is implemented for [`SliceBuffer`] and, with the feature `heapless`, for
+/// `heapless::Vec`.
+#[cfg_attr(
+    feature = "heapless",
+    doc = r##"
+```edition2018
+use skip_error::SkipError;
+let mut rejected: heapless::Vec<&str, 8> = heapless::Vec::new();
+let samples: Vec<u32> = ["1", "two", "3"]
+  .iter()
+  .map(|sample| sample.parse::<u32>().map_err(|_| *sample))
+  .skip_error_into(&mut rejected)
+  .collect();
+assert_eq!(samples, vec![1, 3]);
+assert_eq!(rejected, ["two"]);
+```
+"##
+)]
+pub trait ErrorBuffer<E> {
+    /// Adds `error` to the buffer, or gives it back if the buffer is full.
+    fn push(&mut self, error: E) -> Result<(), E>;
+}
+
+#[cfg(feature = "heapless")]
+impl<E, const N: usize> ErrorBuffer<E> for heapless::Vec<E, N> {
+    fn push(&mut self, error: E) -> Result<(), E> {
+        heapless::Vec::push(self, error)
+    }
+}
+
+/// A buffer of errors stored in a caller-provided slice of uninitialized
+/// slots, for example a `static` or a local array.
+///
+/// ```edition2018
+/// use core::mem::MaybeUninit;
+/// use skip_error::{ErrorBuffer, SliceBuffer};
+/// let mut slots: [MaybeUninit<&str>; 2] = [MaybeUninit::uninit(); 2];
+/// let mut buffer = SliceBuffer::new(&mut slots);
+/// assert_eq!(buffer.push("first"), Ok(()));
+/// assert_eq!(buffer.push("second"), Ok(()));
+/// assert_eq!(buffer.push("third"), Err("third"));
+/// assert_eq!(buffer.errors(), &["first", "second"]);
+/// ```
+pub struct SliceBuffer<'a, E> {
+    slots: &'a mut [MaybeUninit<E>],
+    len: usize,
+}
+
+impl<'a, E> SliceBuffer<'a, E> {
+    /// Creates an empty buffer storing up to `slots.len()` errors.
+    pub fn new(slots: &'a mut [MaybeUninit<E>]) -> Self {
+        SliceBuffer { slots, len: 0 }
+    }
+
+    /// The errors kept so far, in the order they were skipped.
+    pub fn errors(&self) -> &[E] {
+        // SAFETY: the first `len` slots are initialized by `push()`
+        unsafe { core::slice::from_raw_parts(self.slots.as_ptr().cast::<E>(), self.len) }
+    }
+
+    /// The number of errors kept so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no error was kept.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The maximum number of errors which can be kept.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+}
+
+impl<E> ErrorBuffer<E> for SliceBuffer<'_, E> {
+    fn push(&mut self, error: E) -> Result<(), E> {
+        match self.slots.get_mut(self.len) {
+            Some(slot) => {
+                slot.write(error);
+                self.len += 1;
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+}
+
+impl<E> Drop for SliceBuffer<'_, E> {
+    fn drop(&mut self) {
+        for slot in &mut self.slots[..self.len] {
+            // SAFETY: the first `len` slots are initialized by `push()`, and
+            // dropped only once since the buffer is dropped
+            unsafe { slot.assume_init_drop() };
+        }
+    }
+}
+
+/// An iterator keeping the skipped errors in a fixed-capacity buffer, and
+/// counting the errors which did not fit.
+///
+/// This `struct` is created by [`SkipError::skip_error_into()`](crate::SkipError::skip_error_into).
+pub struct SkipErrorInto<'a, I, B> {
+    inner: I,
+    buffer: &'a mut B,
+    report: SkipReport,
+    overflowed: usize,
+}
+
+impl<'a, I, B> SkipErrorInto<'a, I, B> {
+    pub(crate) fn new(inner: I, buffer: &'a mut B) -> Self {
+        SkipErrorInto {
+            inner,
+            buffer,
+            report: SkipReport::default(),
+            overflowed: 0,
+        }
+    }
+
+    /// Returns the [`SkipReport`] of the iteration so far.
+    pub fn report(&self) -> SkipReport {
+        self.report
+    }
+
+    /// The number of skipped errors dropped since the buffer was full.
+    pub fn overflowed(&self) -> usize {
+        self.overflowed
+    }
+}
+
+impl<I, B, T, E> Iterator for SkipErrorInto<'_, I, B>
+where
+    I: Iterator<Item = Result<T, E>>,
+    B: ErrorBuffer<E>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.inner.next()? {
+                Ok(value) => {
+                    self.report.record_ok();
+                    return Some(value);
+                }
+                Err(error) => {
+                    crate::__skipped!(error);
+                    self.report.record_skipped();
+                    if self.buffer.push(error).is_err() {
+                        self.overflowed += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
@@ -0,0 +1,243 @@
+//! Rendering of the skipped errors before they are logged.
+use crate::SkippedError;
+use std::{
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+static ERROR_FORMATTER: RwLock<Option<Arc<dyn ErrorFormatter>>> = RwLock::new(None);
+
+/// Controls how a skipped error is rendered in the logs.
+///
+/// An [`ErrorFormatter`] can be set globally with [`set_error_formatter()`],
+/// or for a single adapter with
+/// [`SkipErrorIter::with_formatter()`](crate::SkipErrorIter::with_formatter).
+/// By default, [`DefaultFormatter`] is used.
+///
+/// The formatter gets the error as a [`fmt::Display`], with its type name and
+/// where it was skipped (see [`SkippedError`]). The chain of the sources of
+/// an [`std::error::Error`] is not available, since the skipped errors only
+/// need to implement [`fmt::Display`]: the errors rendering their chain in
+/// their alternate form, like `anyhow::Error`, can be rendered with
+/// [`AlternateFormatter`], and a single [`Result`] can be logged with its chain
+/// with [`ResultExt::log_err_chain()`](crate::ResultExt::log_err_chain). The
+/// formatter can write a timestamp itself, although the loggers usually add
+/// one.
+///
+/// ```edition2018
+/// use skip_error::{ErrorFormatter, SkipError, SkippedError};
+/// use std::fmt;
+///
+/// struct WithTypeName;
+/// impl ErrorFormatter for WithTypeName {
+///   fn format(&self, f: &mut fmt::Formatter<'_>, skipped: &SkippedError<'_>) -> fmt::Result {
+///     write!(f, "[{}] {}", skipped.type_name(), skipped.error())
+///   }
+/// }
+///
+/// # testing_logger::setup();
+/// let numbers: Vec<u32> = ["1", "2", "three", "4"]
+///   .iter()
+///   .map(|string_number| string_number.parse())
+#[cfg_attr(
+    all(feature = "log", not(feature = "tracing")),
+    doc = "  .skip_error_and_log(log::Level::Warn)"
+)]
+#[cfg_attr(
+    feature = "tracing",
+    doc = "  .skip_error_and_log(tracing::Level::WARN)"
+)]
+///   .with_formatter(WithTypeName)
+///   .collect();
+/// assert_eq!(numbers, vec![1, 2, 4]);
+/// testing_logger::validate(|captured_logs| {
+///   assert!(captured_logs[0].body.starts_with("["));
+///   assert!(captured_logs[0].body.contains("ParseIntError] invalid digit found in string"));
+/// });
+/// ```
+pub trait ErrorFormatter: Send + Sync {
+    /// Writes the rendering of the skipped error into the formatter.
+    fn format(&self, f: &mut fmt::Formatter<'_>, skipped: &SkippedError<'_>) -> fmt::Result;
+}
+
+/// The default [`ErrorFormatter`], rendering the error with its
+/// [`std::fmt::Display`] implementation.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultFormatter;
+
+impl ErrorFormatter for DefaultFormatter {
+    fn format(&self, f: &mut fmt::Formatter<'_>, skipped: &SkippedError<'_>) -> fmt::Result {
+        write!(f, "{}", skipped.error())
+    }
+}
+
+/// An [`ErrorFormatter`] rendering the error with the alternate form of its
+/// [`std::fmt::Display`] implementation, `{:#}`.
+///
+/// For an `anyhow::Error`, it renders the whole chain of contexts on one line
+/// instead of the outermost context only.
+///
+/// ```edition2018
+/// use anyhow::Context;
+/// use skip_error::{AlternateFormatter, SkipError};
+/// # testing_logger::setup();
+/// let numbers: Vec<u32> = ["1", "two", "3"]
+///   .iter()
+///   .map(|string_number| {
+///     string_number
+///       .parse::<u32>()
+///       .with_context(|| format!("invalid line {:?}", string_number))
+///   })
+#[cfg_attr(
+    all(feature = "log", not(feature = "tracing")),
+    doc = "  .skip_error_and_log(log::Level::Warn)"
+)]
+#[cfg_attr(
+    feature = "tracing",
+    doc = "  .skip_error_and_log(tracing::Level::WARN)"
+)]
+///   .with_formatter(AlternateFormatter)
+///   .collect();
+/// assert_eq!(numbers, vec![1, 3]);
+/// testing_logger::validate(|captured_logs| {
+///   assert_eq!(captured_logs[0].body, "invalid line \"two\": invalid digit found in string");
+/// });
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AlternateFormatter;
+
+impl ErrorFormatter for AlternateFormatter {
+    fn format(&self, f: &mut fmt::Formatter<'_>, skipped: &SkippedError<'_>) -> fmt::Result {
+        write!(f, "{:#}", skipped.error())
+    }
+}
+
+/// Sets the [`ErrorFormatter`] used by the macros and the adapters which do not
+/// have their own formatter.
+///
+/// ```edition2018
+/// # #[macro_use]
+/// # extern crate skip_error;
+/// use skip_error::{ErrorFormatter, SkippedError};
+/// use std::fmt;
+///
+/// struct Prefixed;
+/// impl ErrorFormatter for Prefixed {
+///   fn format(&self, f: &mut fmt::Formatter<'_>, skipped: &SkippedError<'_>) -> fmt::Result {
+///     write!(f, "skipped: {}", skipped.error())
+///   }
+/// }
+///
+/// # fn main() {
+/// # testing_logger::setup();
+/// skip_error::set_error_formatter(Prefixed);
+/// for string_number in &["1", "2", "three", "4"] {
+///   let number: u32 = skip_error_and_warn!(string_number.parse());
+/// }
+/// testing_logger::validate(|captured_logs| {
+///   assert_eq!(captured_logs[0].body, "skipped: invalid digit found in string");
+/// });
+/// # }
+/// ```
+///
+/// The formatter can itself replace the global formatter, to render only the
+/// first error differently for example.
+///
+/// ```edition2018
+/// # #[macro_use]
+/// # extern crate skip_error;
+/// use skip_error::{DefaultFormatter, ErrorFormatter, SkippedError};
+/// use std::fmt;
+///
+/// struct FirstOnly;
+/// impl ErrorFormatter for FirstOnly {
+///   fn format(&self, f: &mut fmt::Formatter<'_>, skipped: &SkippedError<'_>) -> fmt::Result {
+///     skip_error::set_error_formatter(DefaultFormatter);
+///     write!(f, "first: {}", skipped.error())
+///   }
+/// }
+///
+/// # fn main() {
+/// # testing_logger::setup();
+/// skip_error::set_error_formatter(FirstOnly);
+/// for string_number in &["one", "two"] {
+///   let number: u32 = skip_error_and_warn!(string_number.parse());
+/// }
+/// testing_logger::validate(|captured_logs| {
+///   assert_eq!(captured_logs[0].body, "first: invalid digit found in string");
+///   assert_eq!(captured_logs[1].body, "invalid digit found in string");
+/// });
+/// # }
+/// ```
+pub fn set_error_formatter<F>(formatter: F)
+where
+    F: ErrorFormatter + 'static,
+{
+    let mut error_formatter = ERROR_FORMATTER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *error_formatter = Some(Arc::new(formatter));
+}
+
+// Returns the formatter set with `set_error_formatter()`, so it can be used
+// without locking for each error.
+#[cfg(feature = "rayon")]
+pub(crate) fn global_formatter() -> Option<Box<dyn ErrorFormatter>> {
+    let formatter = ERROR_FORMATTER
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()?;
+    Some(Box::new(Global(formatter)))
+}
+
+// The global formatter, used by an adapter
+#[cfg(feature = "rayon")]
+struct Global(Arc<dyn ErrorFormatter>);
+
+#[cfg(feature = "rayon")]
+impl ErrorFormatter for Global {
+    fn format(&self, f: &mut fmt::Formatter<'_>, skipped: &SkippedError<'_>) -> fmt::Result {
+        self.0.format(f, skipped)
+    }
+}
+
+#[doc(hidden)]
+pub struct Formatted<'a> {
+    skipped: SkippedError<'a>,
+    formatter: Option<&'a dyn ErrorFormatter>,
+}
+
+impl<'a> Formatted<'a> {
+    pub(crate) fn new(
+        skipped: SkippedError<'a>,
+        formatter: Option<&'a dyn ErrorFormatter>,
+    ) -> Self {
+        Formatted { skipped, formatter }
+    }
+}
+
+impl fmt::Display for Formatted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(formatter) = self.formatter {
+            return formatter.format(f, &self.skipped);
+        }
+        // The lock is released before formatting, so the formatter can call
+        // `set_error_formatter()`
+        let error_formatter = ERROR_FORMATTER
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        match error_formatter.as_deref() {
+            Some(formatter) => formatter.format(f, &self.skipped),
+            None => DefaultFormatter.format(f, &self.skipped),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn formatted<'a, E>(error: &'a E, formatter: Option<&'a dyn ErrorFormatter>) -> Formatted<'a>
+where
+    E: fmt::Display,
+{
+    Formatted::new(SkippedError::new(error, None), formatter)
+}
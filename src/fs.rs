@@ -0,0 +1,230 @@
+//! Skipping the errors of the standard file system.
+use crate::{skipper::Skipper, SkipErrorIter};
+use std::{
+    fs::{DirEntry, ReadDir},
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Returns an iterator over the entries of the directory at `path`, skipping
+/// the entries which could not be read.
+///
+/// The error opening the directory itself is returned, as with
+/// [`std::fs::read_dir()`].
+///
+/// ```edition2018
+/// let path = std::env::temp_dir().join("skip_error_read_dir_skipping");
+/// std::fs::create_dir_all(path.join("child")).unwrap();
+/// let names: Vec<_> = skip_error::fs::read_dir_skipping(&path)
+///   .unwrap()
+///   .map(|entry| entry.file_name())
+///   .collect();
+/// assert_eq!(names, vec!["child"]);
+/// assert!(skip_error::fs::read_dir_skipping(path.join("missing")).is_err());
+/// ```
+#[cfg_attr(
+    feature = "forbid-silent",
+    deprecated(
+        note = "the errors must not be skipped silently, log them with `read_dir_skipping_and_log()`"
+    )
+)]
+pub fn read_dir_skipping<P>(path: P) -> io::Result<SkipErrorIter<ReadDir, DirEntry, io::Error>>
+where
+    P: AsRef<Path>,
+{
+    std::fs::read_dir(path).map(|entries| SkipErrorIter::new(entries, Skipper::new()))
+}
+
+/// Returns an iterator over the entries of the directory at `path`, logging
+/// and skipping the entries which could not be read (see
+/// [`read_dir_skipping()`]).
+///
+/// ```edition2018
+/// let path = std::env::temp_dir().join("skip_error_read_dir_skipping_and_log");
+/// std::fs::create_dir_all(path.join("child")).unwrap();
+/// let entries = skip_error::fs::read_dir_skipping_and_log(
+///   &path,
+#[cfg_attr(
+    all(feature = "log", not(feature = "tracing")),
+    doc = "  log::Level::Warn,"
+)]
+#[cfg_attr(feature = "tracing", doc = "  tracing::Level::WARN,")]
+/// )
+/// .unwrap();
+/// assert_eq!(entries.count(), 1);
+/// ```
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+pub fn read_dir_skipping_and_log<P, L>(
+    path: P,
+    log_level: L,
+) -> io::Result<SkipErrorIter<ReadDir, DirEntry, io::Error>>
+where
+    P: AsRef<Path>,
+    L: Into<crate::Level>,
+{
+    let skipper = Skipper::with_log_level(log_level.into());
+    std::fs::read_dir(path).map(|entries| SkipErrorIter::new(entries, skipper))
+}
+
+/// Returns an iterator over the entries of the directory at `path` and of all
+/// its subdirectories, depth first, skipping the entries which could not be
+/// read and the subdirectories which could not be opened.
+///
+/// The symbolic links are yielded but not followed. The error opening the
+/// directory itself is returned, as with [`std::fs::read_dir()`].
+///
+/// ```edition2018
+/// let path = std::env::temp_dir().join("skip_error_walk_dir_skipping");
+/// std::fs::create_dir_all(path.join("stops/2024")).unwrap();
+/// std::fs::write(path.join("stops/2024/stops.txt"), "").unwrap();
+/// let mut names: Vec<_> = skip_error::fs::walk_dir_skipping(&path)
+///   .unwrap()
+///   .map(|entry| entry.file_name())
+///   .collect();
+/// names.sort();
+/// assert_eq!(names, vec!["2024", "stops", "stops.txt"]);
+/// ```
+#[cfg_attr(
+    feature = "forbid-silent",
+    deprecated(
+        note = "the errors must not be skipped silently, log them with `walk_dir_skipping_and_log()`"
+    )
+)]
+pub fn walk_dir_skipping<P>(path: P) -> io::Result<WalkDir>
+where
+    P: AsRef<Path>,
+{
+    WalkDir::new(path.as_ref(), Skipper::new())
+}
+
+/// Returns an iterator over the entries of the directory at `path` and of all
+/// its subdirectories, logging and skipping the errors (see
+/// [`walk_dir_skipping()`]).
+///
+/// ```edition2018
+/// let path = std::env::temp_dir().join("skip_error_walk_dir_skipping_and_log");
+/// std::fs::create_dir_all(path.join("stops")).unwrap();
+/// let entries = skip_error::fs::walk_dir_skipping_and_log(
+///   &path,
+#[cfg_attr(
+    all(feature = "log", not(feature = "tracing")),
+    doc = "  log::Level::Warn,"
+)]
+#[cfg_attr(feature = "tracing", doc = "  tracing::Level::WARN,")]
+/// )
+/// .unwrap();
+/// assert_eq!(entries.count(), 1);
+/// ```
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+pub fn walk_dir_skipping_and_log<P, L>(path: P, log_level: L) -> io::Result<WalkDir>
+where
+    P: AsRef<Path>,
+    L: Into<crate::Level>,
+{
+    WalkDir::new(path.as_ref(), Skipper::with_log_level(log_level.into()))
+}
+
+/// An iterator over the entries of a directory tree, skipping the errors.
+///
+/// This `struct` is created by [`walk_dir_skipping()`].
+pub struct WalkDir {
+    // The directories being read, with their path and depth
+    stack: Vec<(ReadDir, PathBuf, usize)>,
+    max_depth: usize,
+    skipper: Skipper,
+}
+
+impl WalkDir {
+    skip_adapter_methods!();
+    budget_adapter_methods!();
+
+    fn new(path: &Path, skipper: Skipper) -> io::Result<Self> {
+        let entries = std::fs::read_dir(path)?;
+        Ok(WalkDir {
+            stack: vec![(entries, path.to_path_buf(), 1)],
+            max_depth: usize::MAX,
+            skipper,
+        })
+    }
+
+    /// Descends at most `max_depth` levels below the directory: the entries
+    /// of the directory itself are at depth 1, so a `max_depth` of 1 is like
+    /// [`read_dir_skipping()`].
+    ///
+    /// ```edition2018
+    /// let path = std::env::temp_dir().join("skip_error_walk_dir_max_depth");
+    /// std::fs::create_dir_all(path.join("stops/2024")).unwrap();
+    /// let names: Vec<_> = skip_error::fs::walk_dir_skipping(&path)
+    ///   .unwrap()
+    ///   .max_depth(1)
+    ///   .map(|entry| entry.file_name())
+    ///   .collect();
+    /// assert_eq!(names, vec!["stops"]);
+    /// ```
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+impl Iterator for WalkDir {
+    type Item = DirEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.skipper.exhausted() {
+            let (entries, path, depth) = self.stack.last_mut()?;
+            let depth = *depth;
+            let entry = match entries.next() {
+                Some(Ok(entry)) => entry,
+                Some(Err(error)) => {
+                    self.skipper.skip(&PathError::new(path, &error));
+                    continue;
+                }
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+            let file_type = match entry.file_type() {
+                Ok(file_type) => file_type,
+                Err(error) => {
+                    self.skipper.skip(&PathError::new(&entry.path(), &error));
+                    continue;
+                }
+            };
+            // A subdirectory which could not be opened is skipped, so each
+            // entry is counted once
+            if file_type.is_dir() && depth < self.max_depth {
+                let path = entry.path();
+                match std::fs::read_dir(&path) {
+                    Ok(entries) => self.stack.push((entries, path, depth + 1)),
+                    Err(error) => {
+                        self.skipper.skip(&PathError::new(&path, &error));
+                        continue;
+                    }
+                }
+            }
+            self.skipper.ok();
+            return Some(entry);
+        }
+        None
+    }
+}
+
+// An error of the file system, logged after the path where it happened
+pub(crate) struct PathError<'a> {
+    path: &'a Path,
+    error: &'a io::Error,
+}
+
+impl<'a> PathError<'a> {
+    pub(crate) fn new(path: &'a Path, error: &'a io::Error) -> Self {
+        PathError { path, error }
+    }
+}
+
+impl std::fmt::Display for PathError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.error)
+    }
+}
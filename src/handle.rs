@@ -0,0 +1,90 @@
+//! Handling of the errors by a callback deciding whether to go on.
+use crate::SkipReport;
+use core::ops::ControlFlow;
+
+/// An iterator giving each error to a handler, which decides whether to skip
+/// it and go on, or to stop the iteration.
+///
+/// The errors are recorded before they are given to the handler, like the
+/// errors skipped by the other adapters (by the registry, the metrics or the
+/// skip scopes, for example).
+///
+/// This `struct` is created by [`SkipError::skip_error_and_handle()`](crate::SkipError::skip_error_and_handle).
+#[cfg_attr(
+    feature = "test-util",
+    doc = r##"
+```edition2018
+use skip_error::SkipError;
+use std::ops::ControlFlow;
+let (numbers, skipped) = skip_error::test_util::capture(|| {
+  ["1", "two", "3"]
+    .iter()
+    .map(|string_number| string_number.parse::<u32>())
+    .skip_error_and_handle(|_| ControlFlow::Continue(()))
+    .collect::<Vec<_>>()
+});
+assert_eq!(numbers, vec![1, 3]);
+assert_eq!(skipped.len(), 1);
+```
+"##
+)]
+pub struct HandleErrorIter<I, F> {
+    inner: I,
+    handler: F,
+    report: SkipReport,
+    stopped: bool,
+}
+
+impl<I, F> HandleErrorIter<I, F> {
+    pub(crate) fn new(inner: I, handler: F) -> Self {
+        HandleErrorIter {
+            inner,
+            handler,
+            report: SkipReport::default(),
+            stopped: false,
+        }
+    }
+
+    /// Returns the [`SkipReport`] of the iteration so far, counting the
+    /// errors given to the handler as skipped.
+    pub fn report(&self) -> SkipReport {
+        self.report
+    }
+
+    /// Whether the handler stopped the iteration.
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+}
+
+impl<I, F, T, E> Iterator for HandleErrorIter<I, F>
+where
+    I: Iterator<Item = Result<T, E>>,
+    F: FnMut(E) -> ControlFlow<()>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.stopped {
+            match self.inner.next()? {
+                Ok(value) => {
+                    self.report.record_ok();
+                    return Some(value);
+                }
+                Err(error) => {
+                    crate::__skipped!(error);
+                    self.report.record_skipped();
+                    self.stopped = (self.handler)(error).is_break();
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.stopped {
+            true => (0, Some(0)),
+            false => (0, self.inner.size_hint().1),
+        }
+    }
+}
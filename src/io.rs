@@ -0,0 +1,214 @@
+//! Skipping the errors of the standard I/O.
+use crate::{SkipErrorIter, Skipper};
+use std::io::{self, BufRead, Lines};
+
+/// Trait to extend any [`std::io::BufRead`] with an iterator of its lines,
+/// skipping the lines which could not be read (like an I/O error or invalid
+/// UTF-8).
+///
+/// Note that a reader failing on every read never ends, which can be
+/// prevented with [`SkipErrorIter::error_budget()`].
+pub trait SkipErrorBufReadExt: BufRead + Sized {
+    /// Returns an iterator of the lines of this reader, skipping the errors.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipErrorBufReadExt;
+    /// let reader = std::io::Cursor::new(b"one\ntw\xF0o\nthree\n");
+    /// let lines: Vec<String> = reader.lines_skip_error().collect();
+    /// assert_eq!(lines, vec!["one", "three"]);
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `lines_skip_error_and_log()`"
+        )
+    )]
+    fn lines_skip_error(self) -> SkipErrorIter<Lines<Self>, String, io::Error> {
+        SkipErrorIter::new(self.lines(), Skipper::new())
+    }
+
+    /// Returns an iterator of the lines of this reader, logging and skipping
+    /// the errors (see
+    /// [`SkipError::skip_error_and_log()`](crate::SkipError::skip_error_and_log)).
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipErrorBufReadExt;
+    /// # testing_logger::setup();
+    /// let reader = std::io::Cursor::new(b"one\ntw\xF0o\nthree\n");
+    /// let lines: Vec<String> = reader
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .lines_skip_error_and_log(log::Level::Warn)"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .lines_skip_error_and_log(tracing::Level::WARN)"
+    )]
+    ///   .collect();
+    /// assert_eq!(lines, vec!["one", "three"]);
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs[0].level, log::Level::Warn);
+    ///   assert!(captured_logs[0].body.contains("UTF-8"));
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn lines_skip_error_and_log<L>(
+        self,
+        log_level: L,
+    ) -> SkipErrorIter<Lines<Self>, String, io::Error>
+    where
+        L: Into<crate::Level>,
+    {
+        SkipErrorIter::new(self.lines(), Skipper::with_log_level(log_level.into()))
+    }
+}
+
+impl<R> SkipErrorBufReadExt for R where R: BufRead {}
+
+/// The [`io::ErrorKind`]s skipped by default by
+/// [`SkipIoErrors::skip_io_errors()`], after which the next I/O operations
+/// can still succeed.
+pub const TRANSIENT_ERROR_KINDS: &[io::ErrorKind] = &[
+    io::ErrorKind::Interrupted,
+    io::ErrorKind::WouldBlock,
+    io::ErrorKind::TimedOut,
+];
+
+/// An iterator skipping the I/O errors of some [`io::ErrorKind`]s, and
+/// yielding the other errors.
+///
+/// This `struct` is created by [`SkipIoErrors::skip_io_errors()`].
+pub struct SkipIoErrorsIter<I> {
+    inner: I,
+    kinds: Vec<io::ErrorKind>,
+    skipper: Skipper,
+}
+
+impl<I> SkipIoErrorsIter<I> {
+    skip_adapter_methods!();
+    budget_adapter_methods!();
+
+    /// Skips only the errors of these `kinds`, instead of
+    /// [`TRANSIENT_ERROR_KINDS`].
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipIoErrors;
+    /// use std::io::{Error, ErrorKind};
+    /// let results = vec![
+    ///   Ok(1),
+    ///   Err(Error::from(ErrorKind::NotFound)),
+    ///   Err(Error::from(ErrorKind::Interrupted)),
+    /// ];
+    /// let kinds: Vec<ErrorKind> = results
+    ///   .into_iter()
+    ///   .skip_io_errors()
+    ///   .skipping_kinds(&[ErrorKind::NotFound])
+    ///   .filter_map(|result| result.err().map(|error| error.kind()))
+    ///   .collect();
+    /// assert_eq!(kinds, vec![ErrorKind::Interrupted]);
+    /// ```
+    pub fn skipping_kinds(mut self, kinds: &[io::ErrorKind]) -> Self {
+        self.kinds = kinds.to_vec();
+        self
+    }
+}
+
+impl<I, T> Iterator for SkipIoErrorsIter<I>
+where
+    I: Iterator<Item = io::Result<T>>,
+{
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.skipper.exhausted() {
+            match self.inner.next()? {
+                Ok(value) => {
+                    self.skipper.ok();
+                    return Some(Ok(value));
+                }
+                Err(error) if self.kinds.contains(&error.kind()) => self.skipper.skip(&error),
+                Err(error) => return Some(Err(error)),
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+/// Trait to extend any [`Iterator`] of [`io::Result`] with an adapter
+/// skipping the transient I/O errors only, and yielding the other errors so
+/// they can be propagated.
+///
+/// ```edition2018
+/// use skip_error::SkipIoErrors;
+/// use std::io::{Error, ErrorKind};
+/// let results = vec![
+///   Ok(1),
+///   Err(Error::from(ErrorKind::Interrupted)),
+///   Ok(2),
+///   Err(Error::from(ErrorKind::PermissionDenied)),
+///   Ok(3),
+/// ];
+/// let numbers: std::io::Result<Vec<u32>> = results.into_iter().skip_io_errors().collect();
+/// assert_eq!(numbers.unwrap_err().kind(), ErrorKind::PermissionDenied);
+/// ```
+pub trait SkipIoErrors<T>: Iterator<Item = io::Result<T>> + Sized {
+    /// Returns an iterator skipping the errors of the
+    /// [`TRANSIENT_ERROR_KINDS`].
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `skip_io_errors_and_log()`"
+        )
+    )]
+    fn skip_io_errors(self) -> SkipIoErrorsIter<Self> {
+        SkipIoErrorsIter {
+            inner: self,
+            kinds: TRANSIENT_ERROR_KINDS.to_vec(),
+            skipper: Skipper::new(),
+        }
+    }
+
+    /// Returns an iterator logging and skipping the errors of the
+    /// [`TRANSIENT_ERROR_KINDS`].
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipIoErrors;
+    /// use std::io::{Error, ErrorKind};
+    /// # testing_logger::setup();
+    /// let results = vec![Ok(1), Err(Error::new(ErrorKind::TimedOut, "too slow")), Ok(2)];
+    /// let numbers: Vec<u32> = results
+    ///   .into_iter()
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .skip_io_errors_and_log(log::Level::Warn)"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .skip_io_errors_and_log(tracing::Level::WARN)"
+    )]
+    ///   .collect::<std::io::Result<_>>()
+    ///   .unwrap();
+    /// assert_eq!(numbers, vec![1, 2]);
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs.len(), 1);
+    ///   assert!(captured_logs[0].body.contains("too slow"));
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn skip_io_errors_and_log<L>(self, log_level: L) -> SkipIoErrorsIter<Self>
+    where
+        L: Into<crate::Level>,
+    {
+        SkipIoErrorsIter {
+            inner: self,
+            kinds: TRANSIENT_ERROR_KINDS.to_vec(),
+            skipper: Skipper::with_log_level(log_level.into()),
+        }
+    }
+}
+
+impl<I, T> SkipIoErrors<T> for I where I: Iterator<Item = io::Result<T>> {}
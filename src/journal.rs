@@ -0,0 +1,93 @@
+//! JSON Lines journal of the skipped errors.
+use crate::{SkipSink, SkippedError};
+use std::{
+    io::Write,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A [`SkipSink`] appending one JSON object per skipped error to a writer, in
+/// the [JSON Lines](https://jsonlines.org/) format.
+///
+/// Each object contains the following fields:
+/// - `timestamp`: number of seconds since the UNIX epoch
+/// - `message`: the error, rendered with [`std::fmt::Display`]
+/// - `type`: the name of the type of the error
+/// - `index`: the position of the error in the original iterator
+/// - `context`: the context given with [`JsonJournal::with_context()`], or
+///   `null`
+///
+/// Errors while writing to the journal are ignored.
+///
+/// ```edition2018
+/// use skip_error::{JsonJournal, SkipError};
+/// let path = std::env::temp_dir().join("skip_error_journal_example.jsonl");
+/// let rejects = std::fs::File::create(&path).unwrap();
+/// let numbers: Vec<u32> = ["1", "2", "three", "4"]
+///   .iter()
+///   .map(|string_number| string_number.parse())
+///   .skip_error()
+///   .with_sink(JsonJournal::new(rejects).with_context("loading numbers"))
+///   .collect();
+/// assert_eq!(numbers, vec![1, 2, 4]);
+///
+/// let journal = std::fs::read_to_string(&path).unwrap();
+/// let lines: Vec<serde_json::Value> = journal
+///   .lines()
+///   .map(|line| serde_json::from_str(line).unwrap())
+///   .collect();
+/// assert_eq!(lines.len(), 1);
+/// assert_eq!(lines[0]["message"], "invalid digit found in string");
+/// assert_eq!(lines[0]["index"], 2);
+/// assert_eq!(lines[0]["context"], "loading numbers");
+/// assert!(lines[0]["type"].as_str().unwrap().contains("ParseIntError"));
+/// assert!(lines[0]["timestamp"].is_number());
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct JsonJournal<W> {
+    writer: W,
+    context: Option<String>,
+}
+
+impl<W> JsonJournal<W>
+where
+    W: Write,
+{
+    /// Creates a journal writing into `writer`.
+    pub fn new(writer: W) -> Self {
+        JsonJournal {
+            writer,
+            context: None,
+        }
+    }
+
+    /// Adds a context to each entry of the journal.
+    pub fn with_context<C>(mut self, context: C) -> Self
+    where
+        C: Into<String>,
+    {
+        self.context = Some(context.into());
+        self
+    }
+}
+
+impl<W> SkipSink for JsonJournal<W>
+where
+    W: Write + Send,
+{
+    fn skipped(&mut self, skipped: &SkippedError<'_>) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs_f64())
+            .unwrap_or_default();
+        let entry = serde_json::json!({
+            "timestamp": timestamp,
+            "message": skipped.error().to_string(),
+            "type": skipped.type_name(),
+            "index": skipped.index(),
+            "context": self.context,
+        });
+        if serde_json::to_writer(&mut self.writer, &entry).is_ok() {
+            let _ = self.writer.write_all(b"\n");
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Support for lending iterators of the [`lender`](::lender) crate.
+use crate::skipper::Skipper;
+use ::lender::{Lend, Lender, Lending};
+
+/// A lend which can be turned into a [`Result`].
+///
+/// This is implemented for any [`Result`] where the error implements
+/// [`std::fmt::Display`], and allows [`SkipErrorLender`] to name the type of the
+/// `Ok()` values for each lifetime of the original [`Lender`].
+pub trait ResultLend<'lend> {
+    /// Type of the `Ok()` value.
+    type Ok: 'lend;
+    /// Type of the `Err()` value.
+    type Err: core::fmt::Display + 'lend;
+
+    /// Turns the lend into a [`Result`].
+    fn into_result(self) -> Result<Self::Ok, Self::Err>;
+}
+
+impl<'lend, T, E> ResultLend<'lend> for Result<T, E>
+where
+    T: 'lend,
+    E: core::fmt::Display + 'lend,
+{
+    type Ok = T;
+    type Err = E;
+
+    fn into_result(self) -> Result<T, E> {
+        self
+    }
+}
+
+/// A lender that ignore errors
+pub struct SkipErrorLender<L> {
+    inner: L,
+    skipper: Skipper,
+}
+
+impl<L> SkipErrorLender<L>
+where
+    L: Lender,
+{
+    fn new(inner: L, skipper: Skipper) -> Self {
+        ::lender::__check_lender_covariance::<L>();
+        SkipErrorLender { inner, skipper }
+    }
+
+    skip_adapter_methods!();
+}
+
+impl<'lend, L> Lending<'lend> for SkipErrorLender<L>
+where
+    L: Lender,
+    for<'all> Lend<'all, L>: ResultLend<'all>,
+{
+    type Lend = <Lend<'lend, L> as ResultLend<'lend>>::Ok;
+}
+
+impl<L> Lender for SkipErrorLender<L>
+where
+    L: Lender,
+    for<'all> Lend<'all, L>: ResultLend<'all>,
+{
+    // SAFETY: the lend is the `Ok()` value of the lend of `L`, which is covariant
+    ::lender::unsafe_assume_covariance!();
+
+    fn next(&mut self) -> Option<Lend<'_, Self>> {
+        while let Some(result) = self.inner.next() {
+            let error = match result.into_result() {
+                // SAFETY: polonius return, the lend is only borrowed from `self.inner`
+                Ok(value) => {
+                    self.skipper.ok();
+                    return Some(unsafe {
+                        core::mem::transmute::<Lend<'_, Self>, Lend<'_, Self>>(value)
+                    });
+                }
+                Err(error) => error,
+            };
+            self.skipper.skip(&error);
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.inner.size_hint();
+        (0, upper)
+    }
+}
+
+/// Trait to extend any [`Lender`] where the lend is a [`Result`].
+/// This allows to skip errors and keep only the `Ok()` values.
+///
+/// The following `Lines` lender is reused in the examples below: it lends each
+/// line from an internal buffer, and fails on lines which are not numbers.
+/// ```edition2018
+/// use lender::{Lend, Lender, Lending};
+/// struct Lines {
+///   lines: std::vec::IntoIter<&'static str>,
+///   buffer: String,
+/// }
+/// impl<'lend> Lending<'lend> for Lines {
+///   type Lend = Result<&'lend str, String>;
+/// }
+/// impl Lender for Lines {
+///   lender::check_covariance!();
+///   fn next(&mut self) -> Option<Lend<'_, Self>> {
+///     let line = self.lines.next()?;
+///     self.buffer.clear();
+///     self.buffer.push_str(line);
+///     if self.buffer.parse::<u32>().is_ok() {
+///       Some(Ok(&self.buffer))
+///     } else {
+///       Some(Err(format!("'{}' is not a valid number", self.buffer)))
+///     }
+///   }
+/// }
+/// ```
+pub trait LendingSkipError: Lender + Sized
+where
+    for<'all> Lend<'all, Self>: ResultLend<'all>,
+{
+    /// Skip all errors of the [`Result`] in the original [`Lender`].
+    ///
+    /// ```edition2018
+    /// # use lender::{Lend, Lender, Lending};
+    /// # struct Lines {
+    /// #   lines: std::vec::IntoIter<&'static str>,
+    /// #   buffer: String,
+    /// # }
+    /// # impl<'lend> Lending<'lend> for Lines {
+    /// #   type Lend = Result<&'lend str, String>;
+    /// # }
+    /// # impl Lender for Lines {
+    /// #   lender::check_covariance!();
+    /// #   fn next(&mut self) -> Option<Lend<'_, Self>> {
+    /// #     let line = self.lines.next()?;
+    /// #     self.buffer.clear();
+    /// #     self.buffer.push_str(line);
+    /// #     if self.buffer.parse::<u32>().is_ok() {
+    /// #       Some(Ok(&self.buffer))
+    /// #     } else {
+    /// #       Some(Err(format!("'{}' is not a valid number", self.buffer)))
+    /// #     }
+    /// #   }
+    /// # }
+    /// use skip_error::LendingSkipError;
+    /// let lines = Lines {
+    ///   lines: vec!["1", "2", "three", "4"].into_iter(),
+    ///   buffer: String::new(),
+    /// };
+    /// let mut numbers = Vec::new();
+    /// let mut lender = lines.skip_error();
+    /// while let Some(line) = lender.next() {
+    ///   numbers.push(line.to_string());
+    /// }
+    /// assert_eq!(numbers, vec!["1", "2", "4"]);
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `skip_error_and_log()`"
+        )
+    )]
+    fn skip_error(self) -> SkipErrorLender<Self> {
+        SkipErrorLender::new(self, Skipper::new())
+    }
+
+    /// Skip all errors of the [`Result`] in the original [`Lender`].  This
+    /// also allows to log the errors, choosing which level to use (see
+    /// [`SkipError::skip_error_and_log()`](crate::SkipError::skip_error_and_log)).
+    ///
+    /// ```edition2018
+    /// # use lender::{Lend, Lender, Lending};
+    /// # struct Lines {
+    /// #   lines: std::vec::IntoIter<&'static str>,
+    /// #   buffer: String,
+    /// # }
+    /// # impl<'lend> Lending<'lend> for Lines {
+    /// #   type Lend = Result<&'lend str, String>;
+    /// # }
+    /// # impl Lender for Lines {
+    /// #   lender::check_covariance!();
+    /// #   fn next(&mut self) -> Option<Lend<'_, Self>> {
+    /// #     let line = self.lines.next()?;
+    /// #     self.buffer.clear();
+    /// #     self.buffer.push_str(line);
+    /// #     if self.buffer.parse::<u32>().is_ok() {
+    /// #       Some(Ok(&self.buffer))
+    /// #     } else {
+    /// #       Some(Err(format!("'{}' is not a valid number", self.buffer)))
+    /// #     }
+    /// #   }
+    /// # }
+    /// use skip_error::LendingSkipError;
+    /// # testing_logger::setup();
+    /// let lines = Lines {
+    ///   lines: vec!["1", "2", "three", "4"].into_iter(),
+    ///   buffer: String::new(),
+    /// };
+    /// let mut numbers = Vec::new();
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "let mut lender = lines.skip_error_and_log(log::Level::Warn);"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "let mut lender = lines.skip_error_and_log(tracing::Level::WARN);"
+    )]
+    /// while let Some(line) = lender.next() {
+    ///   numbers.push(line.to_string());
+    /// }
+    /// assert_eq!(numbers, vec!["1", "2", "4"]);
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs[0].level, log::Level::Warn);
+    ///   assert_eq!(captured_logs[0].body, "'three' is not a valid number");
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn skip_error_and_log<V>(self, log_level: V) -> SkipErrorLender<Self>
+    where
+        V: Into<crate::Level>,
+    {
+        SkipErrorLender::new(self, Skipper::with_log_level(log_level.into()))
+    }
+}
+
+impl<L> LendingSkipError for L
+where
+    L: Lender,
+    for<'all> Lend<'all, L>: ResultLend<'all>,
+{
+}
@@ -1,4 +1,5 @@
 #![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 //! This crate provides a single macro to help skipping a error in a loop,
 //! possibly logging it.
@@ -41,7 +42,7 @@
 //! ```
 //!
 #![cfg_attr(
-    any(feature = "log", feature = "tracing"),
+    any(feature = "log", feature = "tracing", feature = "defmt"),
     doc = "
 # Logging
 
@@ -50,14 +51,427 @@ feature `tracing` (see [Features](#features)). See [`skip_error_and_log!`]
 and [`SkipError::skip_error_and_log()`] for more information.
 "
 )]
+#![cfg_attr(
+    feature = "metrics",
+    doc = r##"
+# Metrics
+
+With the feature `metrics`, every skipped error increments the counter
+`skip_error.skipped` of the [`metrics`](https://docs.rs/metrics) facade, with
+a label `target` which is the module where the error was skipped (the same as
+the target of the logs).
+```edition2018
+# #[macro_use]
+# extern crate skip_error;
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+# fn main() {
+let recorder = DebuggingRecorder::new();
+let snapshotter = recorder.snapshotter();
+metrics::with_local_recorder(&recorder, || {
+  for string_number in &["1", "two", "3", "four"] {
+    let number: u32 = skip_error!(string_number.parse());
+  }
+});
+let snapshot = snapshotter.snapshot().into_vec();
+let (key, _, _, value) = &snapshot[0];
+assert_eq!(key.key().name(), "skip_error.skipped");
+// `rust_out` is the name of the crate of this example
+assert_eq!(key.key().labels().next().unwrap().value(), "rust_out");
+assert_eq!(value, &DebugValue::Counter(2));
+# }
+```
+"##
+)]
+#![cfg_attr(
+    feature = "opentelemetry",
+    doc = r##"
+# OpenTelemetry
+
+With the feature `opentelemetry`, every skipped error is recorded as an event
+`skipped_error` of the active [`opentelemetry`](https://docs.rs/opentelemetry)
+span, with the attributes `exception.type` and `exception.message`, so the
+traces show where some data was dropped.
+```edition2018
+use opentelemetry::trace::{Tracer, TracerProvider};
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+use skip_error::SkipError;
+let exporter = InMemorySpanExporter::default();
+let provider = SdkTracerProvider::builder()
+  .with_simple_exporter(exporter.clone())
+  .build();
+let tracer = provider.tracer("import");
+tracer.in_span("parse", |_| {
+  let numbers: Vec<u32> = ["1", "two", "3"]
+    .iter()
+    .map(|string_number| string_number.parse())
+    .skip_error()
+    .collect();
+});
+let spans = exporter.get_finished_spans().unwrap();
+let event = &spans[0].events.events[0];
+assert_eq!(event.name, "skipped_error");
+assert_eq!(event.attributes[1].value.as_str(), "invalid digit found in string");
+```
+"##
+)]
 //! # Features
 //!
+//! - `std`: use the standard library. Enabled by default. Without it, the
+//!   crate is `no_std` and provides the macros, [`SkipError`] and
+//!   [`SkipReport`]; all the other features, except `alloc`, `defmt`,
+//!   `derive`, `fallible-iterator`, `heapless`, `lender` and `nb`, imply
+//!   `std`.
+//! - `alloc`: with `no_std`, add the sinks of the adapters, which need an
+//!   allocator. Implied by `std`.
 //! - `log`: emit log message with the standard `std::log` macro. Disabled by
-//! default.
+//!   default.
 //! - `tracing`: emit traces with the `tracing::trace` macro. Disabled
-//! by default. If both `log` and `tracing` are enabled, then `log` will be
-//! ignored since `tracing` is configured in a compatibility mode with standard
-//! `log`.
+//!   by default. If both `log` and `tracing` are enabled, then `log` will be
+//!   ignored since `tracing` is configured in a compatibility mode with standard
+//!   `log`.
+//! - `cadence`: count the skipped errors with a statsd client of
+//!   [`cadence`](https://docs.rs/cadence), see `set_statsd_client()`.
+//!   Disabled by default.
+//! - `clap-verbosity-flag`: choose the level of the logs from the `-v`/`-q`
+//!   flags of [`clap-verbosity-flag`](https://docs.rs/clap-verbosity-flag)
+//!   with `verbosity_level()`. Disabled by default.
+//! - `defmt`: emit the skipped errors with the [`defmt`](https://docs.rs/defmt)
+//!   macros, at a `DefmtLevel`, on the embedded targets where neither `log`
+//!   nor `tracing` is practical. Disabled by default. It is ignored if `log` or
+//!   `tracing` is enabled. The binaries must provide a `defmt` global logger.
+//! - `derive`: derive [`SkippableError`] for the error types, choosing which
+//!   variants are skipped, and at which level they are logged, with an
+//!   attribute `#[skip]`. Disabled by default.
+//! - `eyre`: render the [`eyre`](https://docs.rs/eyre) reports with their
+//!   report handler once converted into `EyreReport`, keeping the diagnostics
+//!   set up with `color-eyre`. Disabled by default.
+//! - `fallible-iterator`: skip the errors of the iterators of the
+//!   [`fallible-iterator`](https://docs.rs/fallible-iterator) crate with the
+//!   trait `FallibleSkipError`. Disabled by default.
+//! - `forbid-silent`: deprecate the macro `skip_error!`, the methods
+//!   `skip_error()` and all the other functions and methods which skip the
+//!   errors silently (like `lines_skip_error()` or `read_dir_skipping()`), so
+//!   the compiler warns about each of them and steers towards their logging
+//!   variants. Disabled by default.
+//! - `gen-blocks`: add the macro `skip_error_or_yield!` for the nightly
+//!   `gen` blocks. Disabled by default.
+//! - `heapless`: keep the skipped errors in a
+//!   [`heapless::Vec`](https://docs.rs/heapless) with
+//!   [`SkipError::skip_error_into()`], without allocation. Disabled by default.
+//! - `journal`: write the skipped errors into a JSON Lines journal with
+//!   `JsonJournal`. Disabled by default.
+//! - `lender`: extend lending iterators of the [`lender`](https://docs.rs/lender)
+//!   crate with the trait `LendingSkipError`. Disabled by default.
+//! - `metrics`: count the skipped errors with the
+//!   [`metrics`](https://docs.rs/metrics) facade (see [Metrics](#metrics)).
+//!   Disabled by default.
+//! - `miette`: render the [`miette`](https://docs.rs/miette) diagnostics with
+//!   their code, labels and help text once wrapped into `MietteDiagnostic`.
+//!   Disabled by default.
+//! - `nb`: poll again the non-blocking operations of [`nb`](https://docs.rs/nb),
+//!   like the drivers of `embedded-hal`, while they would block, with
+//!   `SkipWouldBlock`. Disabled by default.
+//! - `net`: accept the TCP connections while skipping the transient errors,
+//!   with `SkipErrorTcpListenerExt`. Disabled by default.
+//! - `observer`: give every skipped error, with where and how it was skipped,
+//!   to the observers added with `add_observer()`. Disabled by default. The
+//!   errors must implement [`std::fmt::Display`].
+//! - `opentelemetry`: record every skipped error as an event of the active
+//!   [`opentelemetry`](https://docs.rs/opentelemetry) span (see
+//!   [OpenTelemetry](#opentelemetry)). Disabled by default.
+//! - `prometheus`: count the skipped errors in a
+//!   [`prometheus`](https://docs.rs/prometheus) counter, see
+//!   `prometheus_counter()`. Disabled by default.
+//! - `rayon`: extend the parallel iterators of [`rayon`](https://docs.rs/rayon)
+//!   with the trait `ParallelSkipError`. Disabled by default.
+//! - `registry`: record every skipped error in a thread-local registry, see
+//!   `take_skipped()`. Disabled by default. The errors must implement
+//!   [`std::fmt::Display`].
+//! - `serde`: implement `serde::Serialize` for [`SkipReport`], and
+//!   deserialize the sequences skipping their invalid elements with
+//!   `LenientVec`. Disabled by default.
+//! - `slog`: emit the skipped errors to a [`slog`](https://docs.rs/slog)
+//!   logger, with `skip_error_and_slog!` or the sink `SlogSink`. Disabled by
+//!   default.
+//! - `stream`: extend [`Stream`](https://docs.rs/futures-core) of
+//!   `Result` with the trait `SkipErrorStreamExt`, any `TryStream` with
+//!   the trait `TrySkipErrorStreamExt`, any `Stream` with the concurrent
+//!   fallible maps of `MapConcurrentStreamExt`, any `Sink` with the trait
+//!   `SkipErrorSinkExt`, and any `AsyncBufRead` with the lines of
+//!   `SkipErrorAsyncBufReadExt`. Disabled by default.
+//! - `syslog`: send the skipped errors to the local syslog daemon or to
+//!   systemd-journald with the sink `SyslogSink`, on Unix. Disabled by
+//!   default.
+//! - `timing`: record when the errors are skipped with the method
+//!   `with_timing()` of the adapters, in the report and in the logs. Disabled
+//!   by default.
+//! - `tokio`: integration with the [`tokio`](https://docs.rs/tokio) runtime,
+//!   like `SkipErrorReceiverExt` for the `tokio::sync::mpsc` receivers,
+//!   `SkipErrorJoinSetExt` for the `tokio::task::JoinSet` of fallible tasks,
+//!   `SkipErrorTokioBufReadExt` for the lines of a `tokio::io::AsyncBufRead`,
+//!   `read_dir_skip_error()` to scan a directory, `walk_dir_skip_error()` to
+//!   scan a directory tree, `spawn_blocking_skip_error()` to iterate a
+//!   blocking iterator on the blocking pool, `MpscSink` to forward the skipped errors into a channel, or
+//!   `RetryStreamExt` to retry the transient errors of a stream.
+//!   Implies `stream`. Disabled by default.
+//! - `test-util`: capture the skipped errors in tests, see
+//!   `test_util::capture()`. Disabled by default. The errors must implement
+//!   [`std::fmt::Display`].
+//! - `tracing-error`: capture a `tracing_error::SpanTrace` where each error
+//!   is skipped, emitted in the field `span_trace` of the traces and given to
+//!   the sinks with `SkippedError::span_trace()`. The span traces are empty,
+//!   and not emitted, unless the subscriber has a `tracing_error::ErrorLayer`.
+//!   Implies `tracing`.
+//!   Disabled by default.
+//! - `wasm`: emit the skipped errors to the console of the browser with the
+//!   sink `ConsoleSink`, without configuring a logger. Disabled by default.
+//!
+//! # Failing the tests on skipped errors
+//!
+//! When this crate is compiled with `--cfg skip_error_panic_on_skip`, every
+//! error skipped by the macros or the adapters panics, so the tests fail
+//! loudly if the code under test skipped data they expected to be processed.
+//! Since the features are shared by all the builds, this is a `cfg` to set
+//! only when running the tests:
+//!
+//! ```shell
+//! RUSTFLAGS="--cfg skip_error_panic_on_skip" cargo test
+//! ```
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[macro_use]
+mod skipper;
+use skipper::{SkipDetails, Skipper};
+
+#[cfg(all(
+    feature = "std",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+mod background;
+#[cfg(feature = "cadence")]
+mod cadence_support;
+#[cfg(feature = "cadence")]
+pub use cadence_support::set_statsd_client;
+#[cfg(feature = "std")]
+mod channel;
+#[cfg(feature = "std")]
+pub use channel::SkipErrorStdReceiverExt;
+#[cfg(all(
+    feature = "clap-verbosity-flag",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+mod clap_support;
+#[cfg(all(
+    feature = "clap-verbosity-flag",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+pub use clap_support::verbosity_level;
+mod classify;
+#[doc(hidden)]
+pub use classify::__log_skippable;
+pub use classify::{SkipLevel, SkipSkippable, SkippableError};
+#[cfg(feature = "derive")]
+pub use skip_error_derive::SkippableError;
+#[cfg(feature = "defmt")]
+mod defmt_support;
+#[doc(hidden)]
+#[cfg(feature = "defmt")]
+pub use defmt_support::defmt as __defmt;
+#[cfg(feature = "defmt")]
+pub use defmt_support::DefmtLevel;
+#[cfg(feature = "std")]
+pub mod env;
+#[cfg(feature = "eyre")]
+mod eyre_support;
+#[cfg(feature = "eyre")]
+pub use eyre_support::EyreReport;
+#[cfg(feature = "fallible-iterator")]
+mod fallible_support;
+#[cfg(feature = "fallible-iterator")]
+pub use fallible_support::FallibleSkipError;
+mod fixed;
+pub use fixed::{ErrorBuffer, SkipErrorInto, SliceBuffer};
+#[cfg(all(
+    feature = "std",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+mod format;
+#[doc(hidden)]
+#[cfg(all(
+    feature = "std",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+pub use format::formatted as __formatted;
+#[cfg(all(
+    feature = "std",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+pub use format::{set_error_formatter, AlternateFormatter, DefaultFormatter, ErrorFormatter};
+#[cfg(feature = "std")]
+pub mod fs;
+mod handle;
+pub use handle::HandleErrorIter;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "std")]
+pub use io::{SkipErrorBufReadExt, SkipIoErrors, SkipIoErrorsIter, TRANSIENT_ERROR_KINDS};
+#[cfg(feature = "journal")]
+mod journal;
+#[cfg(feature = "journal")]
+pub use journal::JsonJournal;
+#[cfg(feature = "lender")]
+mod lending;
+#[cfg(feature = "lender")]
+pub use lending::{LendingSkipError, ResultLend, SkipErrorLender};
+#[cfg(feature = "metrics")]
+mod metrics_support;
+#[cfg(feature = "miette")]
+mod miette_support;
+#[cfg(feature = "miette")]
+pub use miette_support::MietteDiagnostic;
+#[cfg(feature = "nb")]
+mod nb_support;
+#[cfg(feature = "nb")]
+pub use nb_support::{SkipWouldBlock, SkipWouldBlockIter};
+#[cfg(feature = "net")]
+mod net;
+#[cfg(feature = "net")]
+pub use net::{IncomingSkipTransient, SkipErrorTcpListenerExt};
+#[cfg(feature = "observer")]
+mod observer;
+#[cfg(feature = "observer")]
+pub use observer::{add_observer, remove_observer, ObserverId, SkipEvent, SkipObserver};
+#[cfg(feature = "opentelemetry")]
+mod opentelemetry_support;
+#[cfg(feature = "std")]
+mod outcome;
+#[allow(deprecated)]
+#[cfg(feature = "std")]
+pub use outcome::run_skipping;
+#[cfg(feature = "std")]
+pub use outcome::SkipOutcome;
+#[cfg(feature = "alloc")]
+mod owned;
+#[cfg(feature = "alloc")]
+pub use owned::{OwnedErrors, SendErrorsAs, SendErrorsExt};
+#[cfg(feature = "std")]
+mod par;
+#[cfg(feature = "std")]
+pub use par::{ParMapResults, ParMapSkipError};
+mod parse;
+pub use parse::SkipErrorParseExt;
+#[cfg(feature = "prometheus")]
+mod prometheus_support;
+#[cfg(feature = "prometheus")]
+pub use prometheus_support::prometheus_counter;
+#[cfg(feature = "rayon")]
+mod rayon_support;
+#[cfg(feature = "rayon")]
+pub use rayon_support::{ParErrorBudget, ParSkipError, ParallelSkipError};
+mod recover;
+pub use recover::RecoverOrSkip;
+#[cfg(feature = "registry")]
+mod registry;
+#[cfg(feature = "registry")]
+pub use registry::take_skipped;
+mod report;
+#[cfg(feature = "std")]
+pub use report::ReportHandle;
+pub use report::{ErrorRateExceeded, NotEnoughSuccesses, SkipReport, TooManyErrors};
+mod result;
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+pub use result::OptionExt;
+pub use result::ResultExt;
+#[cfg(feature = "std")]
+mod retry;
+#[cfg(feature = "std")]
+pub use retry::{Backoff, ConstantBackoff, ExponentialBackoff, MapRetry, NoBackoff, Retry};
+#[cfg(feature = "tokio")]
+pub use retry::{Classifier, IsTransient, RetryPolicy, Transient};
+#[cfg(all(
+    feature = "std",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+mod scope;
+#[cfg(all(
+    feature = "std",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+pub use scope::SkipScope;
+#[cfg(feature = "stream")]
+mod send;
+#[cfg(feature = "stream")]
+pub use send::{SkipErrorSink, SkipErrorSinkExt};
+#[cfg(feature = "serde")]
+mod serde_support;
+#[cfg(feature = "serde")]
+pub use serde_support::{deserialize_lenient_vec, LenientVec};
+mod sink;
+pub use sink::SkippedError;
+#[cfg(feature = "std")]
+pub use sink::{LastErrors, TextSink};
+#[cfg(feature = "alloc")]
+pub use sink::{OwnedSkippedError, SkipSink};
+mod skip_while;
+pub use skip_while::SkipErrorWhile;
+mod skippable;
+pub use skippable::{IntoResults, Skippable, SkippableIterExt};
+#[cfg(feature = "slog")]
+mod slog_support;
+#[cfg(feature = "slog")]
+pub use slog_support::SlogSink;
+#[doc(hidden)]
+#[cfg(feature = "slog")]
+pub use slog_support::{__type_name, slog as __slog};
+#[cfg(feature = "stream")]
+mod split;
+#[cfg(feature = "stream")]
+pub use split::{SplitErr, SplitOk};
+#[cfg(feature = "stream")]
+mod stream;
+#[cfg(feature = "stream")]
+pub use stream::{
+    CollectOkAndErrors, MapConcurrent, MapConcurrentStreamExt, SkipErrorAsyncBufReadExt,
+    SkipErrorStream, SkipErrorStreamExt, TryErrorBudget, TrySkipErrorStream, TrySkipErrorStreamExt,
+};
+#[cfg(all(feature = "syslog", unix))]
+mod syslog_support;
+#[cfg(all(feature = "syslog", unix))]
+pub use syslog_support::{SyslogSeverity, SyslogSink};
+#[cfg(feature = "test-util")]
+pub mod test_util;
+#[cfg(feature = "tokio")]
+mod tokio_support;
+#[allow(deprecated)]
+#[cfg(feature = "tokio")]
+pub use tokio_support::{read_dir_skip_error, spawn_blocking_skip_error, walk_dir_skip_error};
+#[cfg(all(
+    feature = "tokio",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+pub use tokio_support::{
+    read_dir_skip_error_and_log, spawn_blocking_skip_error_and_log, walk_dir_skip_error_and_log,
+};
+#[cfg(feature = "tokio")]
+pub use tokio_support::{
+    JoinSetStream, MpscSink, RetryStreamExt, RetryTransient, SkipErrorJoinSetExt,
+    SkipErrorReceiverExt, SkipErrorTokioBufReadExt, TaskError, ThrottleErrors, WalkDirStream,
+};
+#[doc(hidden)]
+#[cfg(feature = "tracing-error")]
+pub use tracing_error::{SpanTrace as __SpanTrace, SpanTraceStatus as __SpanTraceStatus};
+mod utf8;
+#[allow(deprecated)]
+pub use utf8::decode_utf8_skipping;
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+pub use utf8::decode_utf8_skipping_and_log;
+pub use utf8::{InvalidUtf8, Utf8Skipping};
+#[cfg(feature = "wasm")]
+mod wasm_support;
+#[cfg(feature = "wasm")]
+pub use wasm_support::ConsoleSink;
 
 /// `skip_error` returns the value of a [`Result`] or continues a loop.
 ///
@@ -73,14 +487,38 @@ and [`SkipError::skip_error_and_log()`] for more information.
 /// for string_number in &["1", "2", "three", "4"] {
 ///   let number: u32 = skip_error!(string_number.parse());
 /// }
+///
+/// for row in &[vec!["1", "2"], vec!["3", "four"]] {
+///   let numbers: Vec<u32> = skip_error!(row.iter().map(|s| s.parse::<u32>()).collect());
+///   assert_eq!(numbers, vec![1, 2]);
+/// }
 /// # }
 /// ```
+///
+/// With `skippable:` before the parameter, it accepts any [`Skippable`] type
+/// instead of a [`Result`] (see [`Skippable`] for an example).
+#[cfg_attr(
+    feature = "forbid-silent",
+    deprecated(
+        note = "the errors must not be skipped silently, log them with `skip_error_and_log!`"
+    )
+)]
 #[macro_export]
 macro_rules! skip_error {
+    (skippable: $result:expr) => {{
+        match $crate::Skippable::into_result($result) {
+            Ok(value) => value,
+            Err(error) => {
+                $crate::__skipped!(error);
+                continue;
+            }
+        }
+    }};
     ($result:expr) => {{
         match $result {
             Ok(value) => value,
             Err(error) => {
+                $crate::__skipped!(error);
                 continue;
             }
         }
@@ -91,9 +529,14 @@ macro_rules! skip_error {
 /// the loop.
 ///
 /// `skip_error_and_log` macro takes two parameters. The first argument is of
-/// type [`Result`]. The second argument is anything that can be turned into
+/// type [`Result`], or of any [`Skippable`] type after `skippable:`. The
+/// second argument is anything that can be turned into
 #[cfg_attr(all(feature = "log", not(feature = "tracing")), doc = "[`log::Level`]")]
 #[cfg_attr(feature = "tracing", doc = "[`tracing::Level`]")]
+#[cfg_attr(
+    all(feature = "defmt", not(any(feature = "log", feature = "tracing"))),
+    doc = "[`DefmtLevel`]"
+)]
 /// and defines the level to log to.  The macro returns the value if
 /// [`Result::Ok`] and else, it logs the [`Result::Err`] and calls `continue`.
 ///
@@ -119,22 +562,290 @@ macro_rules! skip_error {
 /// });
 /// # }
 /// ```
+#[cfg_attr(
+    feature = "tracing",
+    doc = r##"
+With `tracing`, the event can be recorded in an explicit parent span, rather
+than in the current span, with a third argument `parent: span`.
+```edition2018
+# #[macro_use]
+# extern crate skip_error;
+# fn main() {
+let import = tracing::info_span!("import");
+for string_number in &["1", "2", "three", "4"] {
+  let number: u32 = skip_error_and_log!(string_number.parse(), tracing::Level::WARN, parent: &import);
+}
+# }
+```
+"##
+)]
+#[doc = r##"
+With a third argument `with_expression`, the expression is logged before the
+error, to tell which call failed when there are several in the same loop.
+```edition2018
+# #[macro_use]
+# extern crate skip_error;
+# fn main() {
+# testing_logger::setup();
+for string_number in &["1", "two"] {
+"##]
+#[cfg_attr(
+    all(feature = "log", not(feature = "tracing")),
+    doc = "  let number: u32 = skip_error_and_log!(string_number.parse(), log::Level::Warn, with_expression);"
+)]
+#[cfg_attr(
+    feature = "tracing",
+    doc = "  let number: u32 = skip_error_and_log!(string_number.parse(), tracing::Level::WARN, with_expression);"
+)]
+#[doc = r##"}
+testing_logger::validate(|captured_logs| {
+  assert!(captured_logs[0].body.contains("string_number.parse(): invalid digit found in string"));
+});
+# }
+```
+"##]
 #[macro_export]
-#[cfg(any(feature = "log", feature = "tracing"))]
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
 macro_rules! skip_error_and_log {
-    ($result:expr, $log_level:expr) => {{
+    (skippable: $result:expr, $log_level:expr, with_expression $(, parent: $parent:expr)?) => {{
+        match $crate::Skippable::into_result($result) {
+            Ok(value) => value,
+            Err(error) => {
+                let log_level = $crate::__level($log_level);
+                $crate::__skipped!(error, log_level);
+                let error = $crate::__WithExpression(stringify!($result), $crate::__formatted(&error, None));
+                $crate::__log!(error, log_level $(; parent: $parent)?);
+                continue;
+            }
+        }
+    }};
+    (skippable: $result:expr, $log_level:expr $(, parent: $parent:expr)?) => {{
+        match $crate::Skippable::into_result($result) {
+            Ok(value) => value,
+            Err(error) => {
+                let log_level = $crate::__level($log_level);
+                $crate::__skipped!(error, log_level);
+                let error = $crate::__formatted(&error, None);
+                $crate::__log!(error, log_level $(; parent: $parent)?);
+                continue;
+            }
+        }
+    }};
+    ($result:expr, $log_level:expr, with_expression $(, parent: $parent:expr)?) => {{
         match $result {
             Ok(value) => value,
             Err(error) => {
-                $crate::__log!(error, $log_level);
+                let log_level = $crate::__level($log_level);
+                $crate::__skipped!(error, log_level);
+                let error = $crate::__WithExpression(stringify!($result), $crate::__formatted(&error, None));
+                $crate::__log!(error, log_level $(; parent: $parent)?);
+                continue;
+            }
+        }
+    }};
+    ($result:expr, $log_level:expr $(, parent: $parent:expr)?) => {{
+        match $result {
+            Ok(value) => value,
+            Err(error) => {
+                let log_level = $crate::__level($log_level);
+                $crate::__skipped!(error, log_level);
+                let error = $crate::__formatted(&error, None);
+                $crate::__log!(error, log_level $(; parent: $parent)?);
                 continue;
             }
         }
     }};
 }
 
+/// `skip_skippable` returns the value of a [`Result`], or skips the error and
+/// continues the loop if it is a skippable [`SkippableError`], logged at its
+/// own level. The fatal errors are returned, converted with [`From`], like with
+/// the operator `?`.
+/// Like [`skip_error!`], it accepts any [`Skippable`] type after
+/// `skippable:`.
+///
+/// ```edition2018
+/// # #[macro_use]
+/// # extern crate skip_error;
+/// use skip_error::{SkipLevel, SkippableError};
+/// use std::fmt;
+///
+/// #[derive(Debug)]
+/// enum ImportError {
+///   InvalidStop,
+///   Disconnected,
+/// }
+/// impl fmt::Display for ImportError {
+///   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///     write!(f, "{:?}", self)
+///   }
+/// }
+/// impl SkippableError for ImportError {
+///   fn is_skippable(&self) -> bool {
+///     matches!(self, ImportError::InvalidStop)
+///   }
+///   fn skip_level(&self) -> Option<SkipLevel> {
+///     Some(SkipLevel::Warn)
+///   }
+/// }
+///
+/// fn import(rows: Vec<Result<u32, ImportError>>) -> Result<Vec<u32>, ImportError> {
+///   let mut stops = Vec::new();
+///   for row in rows {
+///     stops.push(skip_skippable!(row));
+///   }
+///   Ok(stops)
+/// }
+///
+/// # fn main() {
+/// assert_eq!(import(vec![Ok(1), Err(ImportError::InvalidStop), Ok(3)]).unwrap(), vec![1, 3]);
+/// assert!(import(vec![Ok(1), Err(ImportError::Disconnected), Ok(3)]).is_err());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! skip_skippable {
+    (skippable: $result:expr) => {{
+        match $crate::Skippable::into_result($result) {
+            Ok(value) => value,
+            Err(error) => {
+                if !$crate::SkippableError::is_skippable(&error) {
+                    return ::core::result::Result::Err(::core::convert::From::from(error));
+                }
+                $crate::__skipped!(error);
+                $crate::__log_skippable(&error);
+                continue;
+            }
+        }
+    }};
+    ($result:expr) => {{
+        match $result {
+            Ok(value) => value,
+            Err(error) => {
+                if !$crate::SkippableError::is_skippable(&error) {
+                    return ::core::result::Result::Err(::core::convert::From::from(error));
+                }
+                $crate::__skipped!(error);
+                $crate::__log_skippable(&error);
+                continue;
+            }
+        }
+    }};
+}
+
+/// `skip_error_or_yield` yields the value of a [`Result`] in a `gen` block, or
+/// skips the error and falls through to the next `yield`.
+///
+/// Unlike [`skip_error!`], which calls `continue`, it can be used anywhere in
+/// the nightly `gen` blocks and coroutines, within a loop or not. With a second
+/// argument, the errors are logged at this level like with
+/// [`skip_error_and_log!`]. Any [`Skippable`] type is accepted after
+/// `skippable:`.
+///
+/// This needs the nightly feature `gen_blocks`, so the example is only tested
+/// with `RUSTDOCFLAGS="--cfg skip_error_nightly" cargo +nightly test
+/// --features gen-blocks --doc`.
+#[cfg_attr(not(skip_error_nightly), doc = "```ignore")]
+#[cfg_attr(skip_error_nightly, doc = "```edition2024")]
+/// #![feature(gen_blocks)]
+/// # #[macro_use]
+/// # extern crate skip_error;
+/// # fn main() {
+/// let (first, second, third) = ("1", "two", "3");
+/// let numbers: Vec<u32> = gen {
+///   skip_error_or_yield!(first.parse());
+///   skip_error_or_yield!(second.parse());
+///   skip_error_or_yield!(third.parse());
+/// }
+/// .collect();
+/// assert_eq!(numbers, vec![1, 3]);
+/// # }
+/// ```
+#[macro_export]
+#[cfg(feature = "gen-blocks")]
+macro_rules! skip_error_or_yield {
+    (skippable: $result:expr) => {{
+        match $crate::Skippable::into_result($result) {
+            Ok(value) => yield value,
+            Err(error) => $crate::__skipped!(error),
+        }
+    }};
+    (skippable: $result:expr, $log_level:expr) => {{
+        match $crate::Skippable::into_result($result) {
+            Ok(value) => yield value,
+            Err(error) => {
+                let log_level = $crate::__level($log_level);
+                $crate::__skipped!(error, log_level);
+                let error = $crate::__formatted(&error, None);
+                $crate::__log!(error, log_level);
+            }
+        }
+    }};
+    ($result:expr) => {{
+        match $result {
+            Ok(value) => yield value,
+            Err(error) => $crate::__skipped!(error),
+        }
+    }};
+    ($result:expr, $log_level:expr) => {{
+        match $result {
+            Ok(value) => yield value,
+            Err(error) => {
+                let log_level = $crate::__level($log_level);
+                $crate::__skipped!(error, log_level);
+                let error = $crate::__formatted(&error, None);
+                $crate::__log!(error, log_level);
+            }
+        }
+    }};
+}
+
+/// `skip_scope` runs a block in a named [`SkipScope`], counting the errors
+/// skipped inside and logging a single summary when the block ends.
+///
+/// The summary is logged at the warning level, or at the level given as second
+/// argument, and only if some errors were skipped. See [`SkipScope`] for more
+/// information.
+///
+/// ```edition2018
+/// # #[macro_use]
+/// # extern crate skip_error;
+/// use skip_error::SkipError;
+/// # fn main() {
+/// # testing_logger::setup();
+/// let numbers: Vec<u32> = skip_scope!("gtfs import", {
+///   let mut numbers = Vec::new();
+///   for string_number in &["1", "two", "3"] {
+///     numbers.push(skip_error!(string_number.parse()));
+///   }
+///   numbers.extend(["4", "five"].iter().map(|string_number| string_number.parse::<u32>()).skip_error());
+///   numbers
+/// });
+/// assert_eq!(numbers, vec![1, 3, 4]);
+/// testing_logger::validate(|captured_logs| {
+///   assert_eq!(captured_logs.len(), 1);
+///   assert_eq!(captured_logs[0].body, "gtfs import: 2 errors skipped");
+///   assert_eq!(captured_logs[0].level, log::Level::Warn);
+/// });
+/// # }
+/// ```
+#[macro_export]
+#[cfg(all(
+    feature = "std",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+macro_rules! skip_scope {
+    ($name:expr, $body:block) => {{
+        let _scope = $crate::SkipScope::enter($name);
+        $body
+    }};
+    ($name:expr, $log_level:expr, $body:block) => {{
+        let _scope = $crate::SkipScope::enter($name).with_log_level($log_level);
+        $body
+    }};
+}
+
 // Macro to generate new macros
-#[cfg(any(feature = "log", feature = "tracing"))]
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
 macro_rules! skip_error_macro_generation {
     ($macro_name:ident, $log_level:expr) => {
         skip_error_macro_generation!($macro_name, $log_level, $log_level);
@@ -188,6 +899,16 @@ skip_error_macro_generation!(skip_error_and_info, log::Level::Info);
 skip_error_macro_generation!(skip_error_and_debug, log::Level::Debug);
 #[cfg(all(feature = "log", not(feature = "tracing")))]
 skip_error_macro_generation!(skip_error_and_trace, log::Level::Trace);
+#[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+skip_error_macro_generation!(skip_error_and_error, ::skip_error::DefmtLevel::Error);
+#[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+skip_error_macro_generation!(skip_error_and_warn, ::skip_error::DefmtLevel::Warn);
+#[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+skip_error_macro_generation!(skip_error_and_info, ::skip_error::DefmtLevel::Info);
+#[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+skip_error_macro_generation!(skip_error_and_debug, ::skip_error::DefmtLevel::Debug);
+#[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+skip_error_macro_generation!(skip_error_and_trace, ::skip_error::DefmtLevel::Trace);
 #[cfg(feature = "tracing")]
 skip_error_macro_generation!(
     skip_error_and_error,
@@ -211,68 +932,908 @@ skip_error_macro_generation!(
     log::Level::Trace
 );
 
-#[doc(hidden)]
-#[macro_export]
-#[cfg(all(feature = "log", not(feature = "tracing")))]
-macro_rules! __log {
-    ($error:expr, $log_level:expr) => {{
-        log::log!(
-            std::convert::Into::<log::Level>::into($log_level),
-            "{}",
-            $error
-        );
-    }};
+// Without `std`, there is no formatter and the errors are logged as they are
+#[doc(hidden)]
+#[cfg(all(feature = "defmt", not(feature = "std")))]
+pub fn __formatted<E>(error: &E, _formatter: Option<core::convert::Infallible>) -> &E {
+    error
+}
+
+// Counts the skipped error in the open `SkipScope`s, whatever its type. It is
+// called on the error path of all the macros and adapters, which are marked
+// `cold` as a hint for the compiler (see the benchmarks).
+#[doc(hidden)]
+#[cfg_attr(not(skip_error_no_cold), cold)]
+pub fn __scoped() {
+    #[cfg(all(
+        feature = "std",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ))]
+    scope::record();
+}
+
+#[doc(hidden)]
+#[cfg(any(
+    feature = "cadence",
+    feature = "metrics",
+    feature = "observer",
+    feature = "opentelemetry",
+    feature = "prometheus",
+    feature = "registry",
+    feature = "test-util",
+    skip_error_panic_on_skip
+))]
+#[cfg_attr(not(skip_error_no_cold), cold)]
+#[cfg_attr(feature = "observer", track_caller)]
+pub fn __record<E>(error: &E, target: &'static str)
+where
+    E: core::fmt::Display,
+{
+    #[cfg(feature = "observer")]
+    observer::notify(&observer::SkipEvent::new(
+        SkippedError::new(error, None),
+        Some(core::panic::Location::caller()),
+    ));
+    record_as(error, core::any::type_name::<E>(), target);
+}
+
+// The error skipped by a silent macro, recorded with `__record()` if it
+// implements `Display`, or else by the name of its type: the macros don't
+// require `Display`, so enabling a feature doesn't break them.
+#[doc(hidden)]
+#[cfg(any(
+    feature = "cadence",
+    feature = "metrics",
+    feature = "observer",
+    feature = "opentelemetry",
+    feature = "prometheus",
+    feature = "registry",
+    feature = "test-util",
+    skip_error_panic_on_skip
+))]
+pub struct __Skipped<'a, E>(pub &'a E);
+
+#[doc(hidden)]
+#[cfg(any(
+    feature = "cadence",
+    feature = "metrics",
+    feature = "observer",
+    feature = "opentelemetry",
+    feature = "prometheus",
+    feature = "registry",
+    feature = "test-util",
+    skip_error_panic_on_skip
+))]
+pub trait __RecordDisplay {
+    fn __record(&self, target: &'static str);
+}
+
+#[cfg(any(
+    feature = "cadence",
+    feature = "metrics",
+    feature = "observer",
+    feature = "opentelemetry",
+    feature = "prometheus",
+    feature = "registry",
+    feature = "test-util",
+    skip_error_panic_on_skip
+))]
+impl<E> __RecordDisplay for __Skipped<'_, E>
+where
+    E: core::fmt::Display,
+{
+    #[inline]
+    #[cfg_attr(feature = "observer", track_caller)]
+    fn __record(&self, target: &'static str) {
+        __record(self.0, target);
+    }
+}
+
+// Only picked by the method resolution when `__RecordDisplay` doesn't apply,
+// since it needs one more reference
+#[doc(hidden)]
+#[cfg(any(
+    feature = "cadence",
+    feature = "metrics",
+    feature = "observer",
+    feature = "opentelemetry",
+    feature = "prometheus",
+    feature = "registry",
+    feature = "test-util",
+    skip_error_panic_on_skip
+))]
+pub trait __RecordAny {
+    fn __record(&self, target: &'static str);
+}
+
+#[cfg(any(
+    feature = "cadence",
+    feature = "metrics",
+    feature = "observer",
+    feature = "opentelemetry",
+    feature = "prometheus",
+    feature = "registry",
+    feature = "test-util",
+    skip_error_panic_on_skip
+))]
+impl<E> __RecordAny for &__Skipped<'_, E> {
+    #[inline]
+    #[cfg_attr(feature = "observer", track_caller)]
+    fn __record(&self, target: &'static str) {
+        record_unrendered(core::any::type_name::<E>(), target);
+    }
+}
+
+// An error which can't be rendered, displayed as the name of its type
+#[cfg(any(
+    feature = "cadence",
+    feature = "metrics",
+    feature = "observer",
+    feature = "opentelemetry",
+    feature = "prometheus",
+    feature = "registry",
+    feature = "test-util",
+    skip_error_panic_on_skip
+))]
+struct Unrendered(&'static str);
+
+#[cfg(any(
+    feature = "cadence",
+    feature = "metrics",
+    feature = "observer",
+    feature = "opentelemetry",
+    feature = "prometheus",
+    feature = "registry",
+    feature = "test-util",
+    skip_error_panic_on_skip
+))]
+impl core::fmt::Display for Unrendered {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[cfg(any(
+    feature = "cadence",
+    feature = "metrics",
+    feature = "observer",
+    feature = "opentelemetry",
+    feature = "prometheus",
+    feature = "registry",
+    feature = "test-util",
+    skip_error_panic_on_skip
+))]
+#[cfg_attr(not(skip_error_no_cold), cold)]
+#[cfg_attr(feature = "observer", track_caller)]
+fn record_unrendered(type_name: &'static str, target: &'static str) {
+    let error = Unrendered(type_name);
+    #[cfg(feature = "observer")]
+    observer::notify(&observer::SkipEvent::new(
+        SkippedError::new(&error, None).with_type_name(type_name),
+        Some(core::panic::Location::caller()),
+    ));
+    record_as(&error, type_name, target);
+}
+
+// Like `__record()`, for an error logged at `log_level`
+#[doc(hidden)]
+#[cfg(all(
+    any(
+        feature = "cadence",
+        feature = "metrics",
+        feature = "observer",
+        feature = "opentelemetry",
+        feature = "prometheus",
+        feature = "registry",
+        feature = "test-util",
+        skip_error_panic_on_skip
+    ),
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+#[cfg_attr(not(skip_error_no_cold), cold)]
+#[cfg_attr(feature = "observer", track_caller)]
+pub fn __record_at<E>(error: &E, target: &'static str, _log_level: Level)
+where
+    E: core::fmt::Display,
+{
+    #[cfg(feature = "observer")]
+    observer::notify(
+        &observer::SkipEvent::new(
+            SkippedError::new(error, None),
+            Some(core::panic::Location::caller()),
+        )
+        .with_level(Some(_log_level)),
+    );
+    record_as(error, core::any::type_name::<E>(), target);
+}
+
+// Converts the level given to a macro once, since it is used twice
+#[doc(hidden)]
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+pub fn __level<L>(log_level: L) -> Level
+where
+    L: Into<Level>,
+{
+    log_level.into()
+}
+
+// An error logged after the expression which returned it
+#[doc(hidden)]
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+pub struct __WithExpression<D>(pub &'static str, pub D);
+
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+impl<D> core::fmt::Display for __WithExpression<D>
+where
+    D: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}: {}", self.0, self.1)
+    }
+}
+
+// Like `__record()`, for an error whose type is already erased
+#[cfg(any(
+    feature = "cadence",
+    feature = "metrics",
+    feature = "observer",
+    feature = "opentelemetry",
+    feature = "prometheus",
+    feature = "registry",
+    feature = "test-util",
+    skip_error_panic_on_skip
+))]
+#[cfg_attr(not(skip_error_no_cold), cold)]
+pub(crate) fn record_as(
+    _error: &dyn core::fmt::Display,
+    _type_name: &'static str,
+    _target: &'static str,
+) {
+    #[cfg(feature = "cadence")]
+    cadence_support::record();
+    #[cfg(feature = "metrics")]
+    metrics_support::record(_target);
+    #[cfg(feature = "opentelemetry")]
+    opentelemetry_support::record(_error, _type_name);
+    #[cfg(feature = "prometheus")]
+    prometheus_support::record(_target, _type_name);
+    #[cfg(feature = "registry")]
+    registry::record(_error);
+    #[cfg(feature = "test-util")]
+    test_util::record(_error);
+    #[cfg(skip_error_panic_on_skip)]
+    panic!(
+        "error skipped with `--cfg skip_error_panic_on_skip`: {}",
+        _error
+    );
+}
+
+// The target is the module of the call, which is also the target of the logs
+#[doc(hidden)]
+#[macro_export]
+#[cfg(any(
+    feature = "cadence",
+    feature = "metrics",
+    feature = "observer",
+    feature = "opentelemetry",
+    feature = "prometheus",
+    feature = "registry",
+    feature = "test-util",
+    skip_error_panic_on_skip
+))]
+macro_rules! __skipped {
+    ($error:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{__RecordAny as _, __RecordDisplay as _};
+        $crate::__scoped();
+        (&$crate::__Skipped(&$error)).__record(module_path!());
+    }};
+    ($error:expr, $log_level:expr) => {{
+        $crate::__scoped();
+        $crate::__record_at(&$error, module_path!(), $log_level);
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(any(
+    feature = "cadence",
+    feature = "metrics",
+    feature = "observer",
+    feature = "opentelemetry",
+    feature = "prometheus",
+    feature = "registry",
+    feature = "test-util",
+    skip_error_panic_on_skip
+)))]
+macro_rules! __skipped {
+    ($error:expr $(, $log_level:expr)?) => {{
+        $crate::__scoped();
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "log", not(feature = "tracing")))]
+macro_rules! __log {
+    ($error:expr, $log_level:expr) => {{
+        log::log!(
+            ::core::convert::Into::<log::Level>::into($log_level),
+            "{}",
+            $error
+        );
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "tracing", not(feature = "tracing-error")))]
+macro_rules! __log {
+    ($error:tt, $log_level:expr $(; $($field:tt)+)?) => {{
+        match ::core::convert::Into::<tracing::Level>::into($log_level) {
+            tracing::Level::INFO => tracing::info!($($($field)+,)? "{}", $error),
+            tracing::Level::WARN => tracing::warn!($($($field)+,)? "{}", $error),
+            tracing::Level::ERROR => tracing::error!($($($field)+,)? "{}", $error),
+            tracing::Level::DEBUG => tracing::debug!($($($field)+,)? "{}", $error),
+            tracing::Level::TRACE => tracing::trace!($($($field)+,)? "{}", $error),
+        }
+    }};
+}
+
+// The span trace is captured where the error is skipped, and emitted only if
+// the subscriber has an `ErrorLayer`
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "tracing-error")]
+macro_rules! __log {
+    (@tracing $log_level:expr, $($arg:tt)+) => {{
+        match ::core::convert::Into::<tracing::Level>::into($log_level) {
+            tracing::Level::INFO => tracing::info!($($arg)+),
+            tracing::Level::WARN => tracing::warn!($($arg)+),
+            tracing::Level::ERROR => tracing::error!($($arg)+),
+            tracing::Level::DEBUG => tracing::debug!($($arg)+),
+            tracing::Level::TRACE => tracing::trace!($($arg)+),
+        }
+    }};
+    ($error:tt, $log_level:expr $(; $($field:tt)+)?) => {{
+        let span_trace = $crate::__SpanTrace::capture();
+        if span_trace.status() == $crate::__SpanTraceStatus::CAPTURED {
+            $crate::__log!(@tracing $log_level, $($($field)+,)? span_trace = %span_trace, "{}", $error);
+        } else {
+            $crate::__log!(@tracing $log_level, $($($field)+,)? "{}", $error);
+        }
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+macro_rules! __log {
+    ($error:expr, $log_level:expr) => {{
+        let error = $crate::__defmt::Display2Format(&$error);
+        match ::core::convert::Into::<$crate::DefmtLevel>::into($log_level) {
+            $crate::DefmtLevel::Error => $crate::__defmt::error!("{}", error),
+            $crate::DefmtLevel::Warn => $crate::__defmt::warn!("{}", error),
+            $crate::DefmtLevel::Info => $crate::__defmt::info!("{}", error),
+            $crate::DefmtLevel::Debug => $crate::__defmt::debug!("{}", error),
+            $crate::DefmtLevel::Trace => $crate::__defmt::trace!("{}", error),
+        }
+    }};
+}
+
+#[cfg(all(feature = "log", not(feature = "tracing")))]
+pub(crate) type Level = log::Level;
+#[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+pub(crate) type Level = DefmtLevel;
+#[cfg(feature = "tracing")]
+pub(crate) type Level = tracing::Level;
+
+// The fields added to the event of a skipped error, see
+// `SkipErrorIter::with_fields()`
+#[cfg(feature = "tracing")]
+pub(crate) struct Fields(Vec<(&'static str, String)>);
+
+#[cfg(feature = "tracing")]
+type FieldsFn<E> = Box<dyn Fn(&E) -> Fields + Send + Sync>;
+
+// Gives the category of an error, see `SkipErrorIter::classify()`
+#[cfg(feature = "alloc")]
+type CategoryFn<E> = alloc::boxed::Box<dyn Fn(&E) -> &'static str + Send + Sync>;
+
+// Gives the cost of an error, see `SkipErrorIter::budget_with()`
+#[cfg(feature = "alloc")]
+type CostFn<E> = alloc::boxed::Box<dyn Fn(&E) -> usize + Send + Sync>;
+
+// Decides whether an error stops the iteration, see
+// `SkipErrorIter::stop_on_error_matching()`
+#[cfg(feature = "alloc")]
+type StopFn<E> = alloc::boxed::Box<dyn Fn(&E) -> bool + Send + Sync>;
+
+#[cfg(feature = "tracing")]
+impl core::fmt::Display for Fields {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (position, (name, value)) in self.0.iter().enumerate() {
+            if position > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// An iterator that ignore errors
+///
+/// If the error type is uninhabited, like [`core::convert::Infallible`], no
+/// error is ever skipped, but the adapter still counts each value for
+/// [`SkipErrorIter::report()`] and checks whether it is stopped, so it is not
+/// free.
+///
+/// ```edition2018
+/// use skip_error::SkipError;
+/// use std::convert::Infallible;
+/// let mut numbers = vec![1, 2, 3].into_iter().map(Ok::<u32, Infallible>).skip_error();
+/// assert_eq!(numbers.by_ref().sum::<u32>(), 6);
+/// assert_eq!(numbers.report().skipped(), 0);
+/// ```
+#[cfg_attr(
+    feature = "tracing",
+    doc = r##"
+With `tracing`, the skipped errors can be logged inside a span of their own,
+whatever the span in which the values are consumed.
+```edition2018
+use skip_error::SkipError;
+let stage = tracing::info_span!("parse_stage");
+let numbers = ["1", "two", "3"]
+  .iter()
+  .map(|string_number| string_number.parse::<u32>())
+  .skip_error_and_log(tracing::Level::WARN)
+  .in_span(stage);
+let _request = tracing::info_span!("request").entered();
+assert_eq!(numbers.collect::<Vec<_>>(), vec![1, 3]);
+```
+"##
+)]
+pub struct SkipErrorIter<I, T, E>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    inner: I,
+    skipper: Skipper,
+    // The result of the last `peek_ok()`, not yet returned by `next()`
+    peeked: Option<Option<T>>,
+    #[cfg(feature = "tracing")]
+    fields: Option<FieldsFn<E>>,
+    #[cfg(feature = "alloc")]
+    classifier: Option<CategoryFn<E>>,
+    #[cfg(feature = "alloc")]
+    cost: Option<CostFn<E>>,
+    #[cfg(feature = "std")]
+    throttle: Option<core::time::Duration>,
+    #[cfg(feature = "alloc")]
+    stop_on: Option<StopFn<E>>,
+    stopped: bool,
+}
+
+impl<I, T, E> SkipErrorIter<I, T, E>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    skip_adapter_methods!();
+    budget_adapter_methods!();
+
+    fn new(inner: I, skipper: Skipper) -> Self {
+        SkipErrorIter {
+            inner,
+            skipper,
+            peeked: None,
+            #[cfg(feature = "tracing")]
+            fields: None,
+            #[cfg(feature = "alloc")]
+            classifier: None,
+            #[cfg(feature = "alloc")]
+            cost: None,
+            #[cfg(feature = "std")]
+            throttle: None,
+            #[cfg(feature = "alloc")]
+            stop_on: None,
+            stopped: false,
+        }
+    }
+
+    /// Allows to skip errors for a total cost of at most `budget`, where
+    /// `cost` gives the cost of each error: the error exceeding the budget
+    /// is skipped too, but stops the adapter which then yields no more
+    /// values. This tolerates many small problems but few serious ones.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// use std::num::IntErrorKind;
+    /// let serious_cost = 10;
+    /// let numbers: Vec<u32> = ["1", "", "2", "", "3", "four", "5"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error()
+    ///   .budget_with(10, move |error| match error.kind() {
+    ///     IntErrorKind::Empty => 1,
+    ///     _ => serious_cost,
+    ///   })
+    ///   .collect();
+    /// assert_eq!(numbers, vec![1, 2, 3]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn budget_with<F>(mut self, budget: usize, cost: F) -> Self
+    where
+        F: Fn(&E) -> usize + Send + Sync + 'static,
+    {
+        self.skipper.budget = Some(budget);
+        self.cost = Some(alloc::boxed::Box::new(cost));
+        self
+    }
+
+    /// Sleeps for `delay` after each skipped error, with
+    /// [`std::thread::sleep()`], so a source failing immediately and
+    /// persistently (like a closed socket) doesn't spin the loop.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// use std::time::{Duration, Instant};
+    /// let start = Instant::now();
+    /// let numbers: Vec<u32> = ["1", "two", "three", "4"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error()
+    ///   .throttle_errors(Duration::from_millis(10))
+    ///   .collect();
+    /// assert_eq!(numbers, vec![1, 4]);
+    /// assert!(start.elapsed() >= Duration::from_millis(20));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn throttle_errors(mut self, delay: core::time::Duration) -> Self {
+        self.throttle = Some(delay);
+        self
+    }
+
+    /// Stops the iteration at the first error for which `stop_on` returns
+    /// `true`, like an end of stream or a shutdown, while the other errors
+    /// are skipped as usual. This error is not skipped, but is logged as
+    /// `"stopped on: {error}"` if the adapter logs the errors.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// # testing_logger::setup();
+    /// let fatal = vec!["disconnected", "shutdown"];
+    /// let mut messages = vec![Ok(1), Err("invalid message"), Ok(2), Err("shutdown"), Ok(3)]
+    ///   .into_iter()
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .skip_error_and_log(log::Level::Warn)"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .skip_error_and_log(tracing::Level::WARN)"
+    )]
+    #[cfg_attr(
+        not(any(feature = "log", feature = "tracing")),
+        doc = "  .skip_error()"
+    )]
+    ///   .stop_on_error_matching(move |error| fatal.contains(error));
+    /// assert_eq!(messages.by_ref().collect::<Vec<_>>(), vec![1, 2]);
+    /// assert!(messages.is_stopped());
+    /// assert_eq!(messages.report().skipped(), 1);
+    #[cfg_attr(
+        any(feature = "log", feature = "tracing"),
+        doc = r#"testing_logger::validate(|captured_logs| {
+  assert_eq!(captured_logs[1].body, "stopped on: shutdown");
+});"#
+    )]
+    /// ```
+    ///
+    /// The error is rendered like the skipped errors, with `redact_with()`,
+    /// `truncate_errors()` and `with_formatter()`.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// # testing_logger::setup();
+    /// let messages: Vec<u32> = vec![Ok(1), Err("revoked token 4f2a"), Ok(2)]
+    ///   .into_iter()
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .skip_error_and_log(log::Level::Warn)"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .skip_error_and_log(tracing::Level::WARN)"
+    )]
+    #[cfg_attr(
+        not(any(feature = "log", feature = "tracing")),
+        doc = "  .skip_error()"
+    )]
+    ///   .redact_with(|error| error.to_string().replace("4f2a", "***"))
+    ///   .stop_on_error_matching(|error| error.starts_with("revoked"))
+    ///   .collect();
+    /// assert_eq!(messages, vec![1]);
+    #[cfg_attr(
+        any(feature = "log", feature = "tracing"),
+        doc = r#"testing_logger::validate(|captured_logs| {
+  assert_eq!(captured_logs[0].body, "stopped on: revoked token ***");
+});"#
+    )]
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn stop_on_error_matching<F>(mut self, stop_on: F) -> Self
+    where
+        F: Fn(&E) -> bool + Send + Sync + 'static,
+    {
+        self.stop_on = Some(alloc::boxed::Box::new(stop_on));
+        self
+    }
+
+    /// Returns whether the iteration was stopped by an error, see
+    /// [`SkipErrorIter::stop_on_error_matching()`].
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// Classifies each skipped error in a category, like `"parse"` or `"io"`,
+    /// to count the skipped errors per category with
+    /// [`SkipErrorIter::skipped_by_category()`]. The category is also logged
+    /// before the error, like `"parse: invalid digit found in string"`.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// use std::num::IntErrorKind;
+    /// let parse = "parse";
+    /// let mut numbers = ["1", "", "three", "4", "five"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error()
+    ///   .classify(move |error| match error.kind() {
+    ///     IntErrorKind::Empty => "missing",
+    ///     _ => parse,
+    ///   });
+    /// numbers.by_ref().for_each(drop);
+    /// assert_eq!(numbers.skipped_by_category()["parse"], 2);
+    /// assert_eq!(numbers.skipped_by_category()["missing"], 1);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn classify<F>(mut self, classifier: F) -> Self
+    where
+        F: Fn(&E) -> &'static str + Send + Sync + 'static,
+    {
+        self.classifier = Some(alloc::boxed::Box::new(classifier));
+        self
+    }
+
+    /// Returns the number of skipped errors per category, see
+    /// [`SkipErrorIter::classify()`].
+    #[cfg(feature = "alloc")]
+    pub fn skipped_by_category(&self) -> &alloc::collections::BTreeMap<&'static str, usize> {
+        &self.skipper.categories
+    }
+
+    /// Adds the fields computed by `fields` from each skipped error to its
+    /// `tracing` event.
+    ///
+    /// The names of the fields of a `tracing` event must be known at compile
+    /// time, so these fields are emitted together in a field `fields`, like
+    /// `fields="row_id=3 kind=parse"`.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// use std::num::ParseIntError;
+    /// # testing_logger::setup();
+    /// let numbers: Vec<u32> = ["1", "two", "3"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error_and_log(tracing::Level::WARN)
+    ///   .with_fields(|error: &ParseIntError| [("kind", format!("{:?}", error.kind()))])
+    ///   .collect();
+    /// assert_eq!(numbers, vec![1, 3]);
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert!(captured_logs[0].body.contains("kind=InvalidDigit"));
+    /// });
+    /// ```
+    #[cfg(feature = "tracing")]
+    pub fn with_fields<F, Fi, V>(mut self, fields: F) -> Self
+    where
+        F: Fn(&E) -> Fi + Send + Sync + 'static,
+        Fi: IntoIterator<Item = (&'static str, V)>,
+        V: core::fmt::Display,
+    {
+        self.fields = Some(Box::new(move |error| {
+            Fields(
+                fields(error)
+                    .into_iter()
+                    .map(|(name, value)| (name, value.to_string()))
+                    .collect(),
+            )
+        }));
+        self
+    }
 }
 
-#[doc(hidden)]
-#[macro_export]
 #[cfg(feature = "tracing")]
-macro_rules! __log {
-    ($error:tt, $log_level:expr) => {{
-        match std::convert::Into::<tracing::Level>::into($log_level) {
-            tracing::Level::INFO => tracing::info!("{}", $error),
-            tracing::Level::WARN => tracing::warn!("{}", $error),
-            tracing::Level::ERROR => tracing::error!("{}", $error),
-            tracing::Level::DEBUG => tracing::debug!("{}", $error),
-            tracing::Level::TRACE => tracing::trace!("{}", $error),
-        }
-    }};
+impl<I, T, E> SkipErrorIter<I, T, E>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    span_adapter_methods!();
 }
 
-/// An iterator that ignore errors
-pub struct SkipErrorIter<I, T, E>
+impl<I, T, E> SkipErrorIter<I, T, E>
 where
     I: Iterator<Item = Result<T, E>>,
+    E: core::fmt::Display,
 {
-    inner: I,
-    #[cfg(all(feature = "log", not(feature = "tracing")))]
-    log_level: Option<log::Level>,
-    #[cfg(feature = "tracing")]
-    log_level: Option<tracing::Level>,
+    /// Returns a reference to the next value without consuming it, like
+    /// [`Peekable::peek()`](core::iter::Peekable::peek).
+    ///
+    /// The errors before the next value are skipped, and logged, by the call to
+    /// `peek_ok()`: they are not skipped again by the next call to `next()`.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// let mut numbers = ["1", "two", "3"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error();
+    /// assert_eq!(numbers.next(), Some(1));
+    /// assert_eq!(numbers.peek_ok(), Some(&3));
+    /// assert_eq!(numbers.report().skipped(), 1);
+    /// assert_eq!(numbers.next(), Some(3));
+    /// assert_eq!(numbers.peek_ok(), None);
+    /// assert_eq!(numbers.report().skipped(), 1);
+    /// ```
+    pub fn peek_ok(&mut self) -> Option<&T> {
+        if self.peeked.is_none() {
+            let next = self.next();
+            self.peeked = Some(next);
+        }
+        self.peeked.as_ref().and_then(Option::as_ref)
+    }
+
+    /// Collects all the values, skipping the errors, but returns an error if
+    /// fewer than `min_ok` values were kept: losing a few items is fine, but
+    /// not (nearly) all of them.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// let numbers: Result<Vec<u32>, _> = ["1", "two", "3", "4"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error()
+    ///   .require_min_ok(2);
+    /// assert_eq!(numbers.unwrap(), vec![1, 3, 4]);
+    /// ```
+    pub fn require_min_ok<C>(mut self, min_ok: usize) -> Result<C, NotEnoughSuccesses>
+    where
+        C: core::iter::FromIterator<T>,
+    {
+        let collection = self.by_ref().collect();
+        let report = self.report();
+        match report.ok() >= min_ok {
+            true => Ok(collection),
+            false => Err(NotEnoughSuccesses::new(min_ok, report)),
+        }
+    }
+
+    /// Tests whether `predicate` holds for any value, skipping the errors,
+    /// and returns the result along with the number of errors skipped. Like
+    /// [`Iterator::any()`], it stops at the first value for which
+    /// `predicate` holds.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// let (found, skipped) = ["1", "two", "30", "four"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error()
+    ///   .any_ok(|number| number > 10);
+    /// assert_eq!((found, skipped), (true, 1));
+    /// ```
+    pub fn any_ok<P>(mut self, predicate: P) -> (bool, usize)
+    where
+        P: FnMut(T) -> bool,
+    {
+        let any = self.any(predicate);
+        (any, self.report().skipped())
+    }
+
+    /// Tests whether `predicate` holds for all the values, skipping the
+    /// errors, and returns the result along with the number of errors
+    /// skipped. Like [`Iterator::all()`], it stops at the first value for
+    /// which `predicate` does not hold.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// # testing_logger::setup();
+    /// let (valid, skipped) = ["1", "two", "3", "four"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .skip_error_and_log(log::Level::Warn)"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .skip_error_and_log(tracing::Level::WARN)"
+    )]
+    #[cfg_attr(
+        not(any(feature = "log", feature = "tracing")),
+        doc = "  .skip_error()"
+    )]
+    ///   .all_ok(|number| number < 10);
+    /// assert_eq!((valid, skipped), (true, 2));
+    /// ```
+    pub fn all_ok<P>(mut self, predicate: P) -> (bool, usize)
+    where
+        P: FnMut(T) -> bool,
+    {
+        let all = self.all(predicate);
+        (all, self.report().skipped())
+    }
 }
 
-impl<I, T, E> std::iter::Iterator for SkipErrorIter<I, T, E>
+impl<I, T, E> core::iter::Iterator for SkipErrorIter<I, T, E>
 where
     I: Iterator<Item = Result<T, E>>,
-    E: std::fmt::Display,
+    E: core::fmt::Display,
 {
     type Item = T;
 
+    #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next().and_then(|result| match result {
-            Ok(value) => Some(value),
-            Err(_error) => {
-                #[cfg(any(feature = "log", feature = "tracing"))]
-                if let Some(log_level) = self.log_level {
-                    __log!(_error, log_level);
+        if let Some(peeked) = self.peeked.take() {
+            return peeked;
+        }
+        while !self.stopped && !self.skipper.exhausted() {
+            match self.inner.next()? {
+                Ok(value) => {
+                    self.skipper.ok();
+                    return Some(value);
+                }
+                #[cfg(feature = "alloc")]
+                Err(error) if self.stop_on.as_ref().is_some_and(|stop_on| stop_on(&error)) => {
+                    self.stopped = true;
+                    self.skipper.stop(&error);
+                }
+                Err(error) => {
+                    let details = SkipDetails {
+                        #[cfg(feature = "tracing")]
+                        fields: self.fields.as_ref().map(|fields| fields(&error)),
+                        #[cfg(feature = "alloc")]
+                        category: self
+                            .classifier
+                            .as_ref()
+                            .map(|classifier| classifier(&error)),
+                        #[cfg(feature = "alloc")]
+                        cost: self.cost.as_ref().map(|cost| cost(&error)),
+                        #[cfg(not(feature = "alloc"))]
+                        cost: None,
+                    };
+                    self.skipper.skip_with(&error, details);
+                    #[cfg(feature = "std")]
+                    if let Some(delay) = self.throttle {
+                        std::thread::sleep(delay);
+                    }
                 }
-                self.next()
             }
-        })
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.inner.size_hint();
+        match self.peeked {
+            Some(None) => (0, Some(0)),
+            Some(Some(_)) => (1, upper.and_then(|upper| upper.checked_add(1))),
+            None => (0, upper),
+        }
     }
 }
 
-#[cfg(any(feature = "log", feature = "tracing"))]
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
 macro_rules! default_impl_skip_error_iterator {
     ($method_name:ident, $log_level:expr) => {
         default_impl_skip_error_iterator!($method_name, $log_level, $log_level);
@@ -306,6 +1867,17 @@ macro_rules! default_impl_skip_error_iterator {
 
 /// Trait to extend any [`Iterator`] where the [`Iterator::Item`] is a [`Result`].
 /// This allows to skip errors and keep only the `Ok()` values.
+///
+/// It is implemented for any [`IntoIterator`] of [`Result`], so the
+/// collections of results can skip their errors without an explicit
+/// `into_iter()`.
+///
+/// ```edition2018
+/// use skip_error::SkipError;
+/// let results: Vec<Result<u32, &str>> = vec![Ok(1), Err("two"), Ok(3)];
+/// let numbers: Vec<u32> = results.skip_error().collect();
+/// assert_eq!(numbers, vec![1, 3]);
+/// ```
 pub trait SkipError<I, T, E>: Sized
 where
     I: Iterator<Item = Result<T, E>>,
@@ -328,6 +1900,12 @@ where
     ///   .collect();
     /// assert_eq!(v, vec![0,0,0]);
     /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `skip_error_and_log()`"
+        )
+    )]
     fn skip_error(self) -> SkipErrorIter<I, T, E>;
 
     /// Skip all errors of the [`Result`] in the original [`Iterator`].  This
@@ -389,6 +1967,286 @@ where
     fn skip_error_and_log<L>(self, trace_level: L) -> SkipErrorIter<I, T, E>
     where
         L: Into<tracing::Level>;
+    ///
+    /// Skip all errors of the [`Result`] in the original [`Iterator`].  This
+    /// also allows to emit the errors with `defmt`, choosing which
+    /// [`DefmtLevel`] to use.
+    ///
+    /// ```edition2018,no_run
+    /// use skip_error::{DefmtLevel, SkipError};
+    /// let v: Vec<usize> = vec![0,1,0,0,3]
+    ///   .into_iter()
+    ///   .map(|v|
+    ///     if v == 0 {
+    ///       Ok(0)
+    ///     } else {
+    ///       Err(format!("Boom on {}", v))
+    ///     }
+    ///   )
+    ///   .skip_error_and_log(DefmtLevel::Warn)
+    ///   .collect();
+    /// assert_eq!(v, vec![0,0,0]);
+    /// ```
+    #[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+    fn skip_error_and_log<L>(self, defmt_level: L) -> SkipErrorIter<I, T, E>
+    where
+        L: Into<DefmtLevel>;
+
+    /// Give each error to `handler`, which returns
+    /// [`ControlFlow::Continue`](core::ops::ControlFlow::Continue) to skip the
+    /// error and go on, or [`ControlFlow::Break`](core::ops::ControlFlow::Break)
+    /// to stop the iteration after this error.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// use std::ops::ControlFlow;
+    /// let mut invalid = Vec::new();
+    /// let numbers: Vec<u32> = ["1", "two", "3", "", "5"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>().map_err(|_| *string_number))
+    ///   .skip_error_and_handle(|string_number| {
+    ///     invalid.push(string_number);
+    ///     match string_number.is_empty() {
+    ///       true => ControlFlow::Break(()),
+    ///       false => ControlFlow::Continue(()),
+    ///     }
+    ///   })
+    ///   .collect();
+    /// assert_eq!(numbers, vec![1, 3]);
+    /// assert_eq!(invalid, vec!["two", ""]);
+    /// ```
+    fn skip_error_and_handle<F>(self, handler: F) -> HandleErrorIter<I, F>
+    where
+        F: FnMut(E) -> core::ops::ControlFlow<()>;
+
+    /// Skip the errors while `predicate` holds, then yield them: `predicate`
+    /// is called before each item with the [`SkipReport`] so far, and once it
+    /// returns `false` the errors are never skipped again.
+    ///
+    /// This allows to be lenient with a header, for example, and strict with
+    /// the body. The skipped errors can be logged with
+    /// `SkipErrorWhile::set_log_level()`.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// let numbers: Result<Vec<u32>, _> = ["# header", "1", "2", "three", "4"]
+    ///   .iter()
+    ///   .map(|line| line.parse::<u32>())
+    ///   .skip_error_while(|report| report.ok() == 0)
+    ///   .collect();
+    /// assert!(numbers.is_err());
+    /// ```
+    fn skip_error_while<P>(self, predicate: P) -> SkipErrorWhile<I, P>
+    where
+        P: FnMut(&SkipReport) -> bool;
+
+    /// Give each error to `recover`, which returns `Some(value)` to yield this
+    /// value in place of the error, or `None` to skip the error.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// use std::num::IntErrorKind;
+    /// let mut numbers = ["1", "", "three", "4"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .recover_or_skip(|error| match error.kind() {
+    ///     IntErrorKind::Empty => Some(0),
+    ///     _ => None,
+    ///   });
+    /// assert_eq!(numbers.by_ref().collect::<Vec<_>>(), vec![1, 0, 4]);
+    /// assert_eq!(numbers.recovered(), 1);
+    /// assert_eq!(numbers.report().skipped(), 1);
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `recover_or_skip_and_log()`"
+        )
+    )]
+    fn recover_or_skip<F>(self, recover: F) -> RecoverOrSkip<I, F>
+    where
+        F: FnMut(&E) -> Option<T>;
+
+    /// Skip the errors which are skippable according to [`SkippableError`],
+    /// each logged at its own level, and yield the fatal ones, so they can be
+    /// handled.
+    ///
+    /// ```edition2018
+    /// use skip_error::{SkipError, SkipLevel, SkippableError};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum ImportError {
+    ///   InvalidStop,
+    ///   Disconnected,
+    /// }
+    /// # impl std::fmt::Display for ImportError {
+    /// #   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// #     write!(f, "{:?}", self)
+    /// #   }
+    /// # }
+    /// impl SkippableError for ImportError {
+    ///   fn is_skippable(&self) -> bool {
+    ///     *self == ImportError::InvalidStop
+    ///   }
+    ///   fn skip_level(&self) -> Option<SkipLevel> {
+    ///     Some(SkipLevel::Warn)
+    ///   }
+    /// }
+    ///
+    /// let rows = vec![Ok(1), Err(ImportError::InvalidStop), Ok(3), Err(ImportError::Disconnected)];
+    /// let rows: Result<Vec<u32>, _> = rows.into_iter().skip_skippable().collect();
+    /// assert_eq!(rows, Err(ImportError::Disconnected));
+    /// ```
+    fn skip_skippable(self) -> SkipSkippable<I>;
+
+    /// Like [`SkipError::recover_or_skip()`], but logs the skipped errors at
+    /// `log_level`, the recovered ones are not logged.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// # testing_logger::setup();
+    /// let numbers: Vec<u32> = ["1", "", "three"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .recover_or_skip_and_log(|error| error.to_string().contains(\"empty\").then(|| 0), log::Level::Warn)"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .recover_or_skip_and_log(|error| error.to_string().contains(\"empty\").then(|| 0), tracing::Level::WARN)"
+    )]
+    ///   .collect();
+    /// assert_eq!(numbers, vec![1, 0]);
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs.len(), 1);
+    ///   assert_eq!(captured_logs[0].body, "invalid digit found in string");
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn recover_or_skip_and_log<F, L>(self, recover: F, log_level: L) -> RecoverOrSkip<I, F>
+    where
+        F: FnMut(&E) -> Option<T>,
+        L: Into<Level>;
+
+    /// Skip all errors, keeping them in `buffer` without allocating, like a
+    /// [`SliceBuffer`] or, with the feature `heapless`, a `heapless::Vec`.
+    /// The errors skipped once the buffer is full are only counted, see
+    /// [`SkipErrorInto::overflowed()`].
+    ///
+    /// ```edition2018
+    /// use core::mem::MaybeUninit;
+    /// use skip_error::{SkipError, SliceBuffer};
+    /// let mut slots: [MaybeUninit<&str>; 2] = [MaybeUninit::uninit(); 2];
+    /// let mut rejected = SliceBuffer::new(&mut slots);
+    /// let mut samples = ["1", "two", "3", "four", "five"]
+    ///   .iter()
+    ///   .map(|sample| sample.parse::<u32>().map_err(|_| *sample))
+    ///   .skip_error_into(&mut rejected);
+    /// let sum: u32 = samples.by_ref().sum();
+    /// assert_eq!(sum, 4);
+    /// assert_eq!(samples.overflowed(), 1);
+    /// drop(samples);
+    /// assert_eq!(rejected.errors(), &["two", "four"]);
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `skip_error_and_log()`"
+        )
+    )]
+    fn skip_error_into<B>(self, buffer: &mut B) -> SkipErrorInto<'_, I, B>
+    where
+        B: ErrorBuffer<E>;
+
+    /// Like [`SkipError::skip_error()`], but returns a boxed iterator, whose
+    /// type can be named in the fields of a `struct` or in a trait.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// struct Stops {
+    ///   ids: Box<dyn Iterator<Item = u32> + Send>,
+    /// }
+    /// let stops = Stops {
+    ///   ids: vec!["1", "two", "3"]
+    ///     .into_iter()
+    ///     .map(|string_number| string_number.parse::<u32>())
+    ///     .skip_error_boxed(),
+    /// };
+    /// assert_eq!(stops.ids.collect::<Vec<_>>(), vec![1, 3]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `skip_error_boxed_and_log()`"
+        )
+    )]
+    fn skip_error_boxed<'a>(self) -> alloc::boxed::Box<dyn Iterator<Item = T> + Send + 'a>
+    where
+        I: Send + 'a,
+        T: Send + 'a,
+        E: core::fmt::Display + 'a;
+
+    /// Like [`SkipError::skip_error_and_log()`], but returns a boxed
+    /// iterator, see [`SkipError::skip_error_boxed()`].
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// # testing_logger::setup();
+    /// let numbers: Box<dyn Iterator<Item = u32> + Send> = vec!["1", "two", "3"]
+    ///   .into_iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .skip_error_boxed_and_log(log::Level::Warn);"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .skip_error_boxed_and_log(tracing::Level::WARN);"
+    )]
+    /// assert_eq!(numbers.collect::<Vec<_>>(), vec![1, 3]);
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs[0].body, "invalid digit found in string");
+    /// });
+    /// ```
+    #[cfg(all(
+        feature = "alloc",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ))]
+    fn skip_error_boxed_and_log<'a, L>(
+        self,
+        log_level: L,
+    ) -> alloc::boxed::Box<dyn Iterator<Item = T> + Send + 'a>
+    where
+        I: Send + 'a,
+        T: Send + 'a,
+        E: core::fmt::Display + 'a,
+        L: Into<Level>;
+
+    /// Processes all the items and collects the values, or returns all the
+    /// errors if any item failed: unlike [`Iterator::collect()`] into a
+    /// [`Result`], the iteration does not stop at the first error, so they
+    /// can all be reported.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// let numbers: Result<Vec<u32>, _> = ["1", "two", "3", "four"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>().map_err(|_| *string_number))
+    ///   .try_collect_accumulating();
+    /// assert_eq!(numbers, Err(vec!["two", "four"]));
+    ///
+    /// let numbers: Result<Vec<u32>, Vec<_>> = ["1", "3"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .try_collect_accumulating();
+    /// assert_eq!(numbers, Ok(vec![1, 3]));
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn try_collect_accumulating<C>(self) -> Result<C, alloc::vec::Vec<E>>
+    where
+        C: core::iter::FromIterator<T>;
 
     #[cfg(all(feature = "log", not(feature = "tracing")))]
     default_impl_skip_error_iterator!(skip_error_and_trace, log::Level::Trace);
@@ -422,37 +2280,229 @@ where
     default_impl_skip_error_iterator!(skip_error_and_warn, tracing::Level::WARN, log::Level::Warn);
     #[cfg(feature = "tracing")]
     default_impl_skip_error_iterator!(skip_error_and_info, tracing::Level::INFO, log::Level::Info);
+    #[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+    default_impl_skip_error_iterator!(skip_error_and_trace, DefmtLevel::Trace);
+    #[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+    default_impl_skip_error_iterator!(skip_error_and_debug, DefmtLevel::Debug);
+    #[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+    default_impl_skip_error_iterator!(skip_error_and_error, DefmtLevel::Error);
+    #[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+    default_impl_skip_error_iterator!(skip_error_and_warn, DefmtLevel::Warn);
+    #[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+    default_impl_skip_error_iterator!(skip_error_and_info, DefmtLevel::Info);
 }
 
-impl<I, T, E> SkipError<I, T, E> for I
+impl<R, T, E> SkipError<R::IntoIter, T, E> for R
 where
-    I: Iterator<Item = Result<T, E>>,
+    R: IntoIterator<Item = Result<T, E>>,
 {
-    fn skip_error(self) -> SkipErrorIter<I, T, E> {
-        SkipErrorIter {
-            inner: self,
-            #[cfg(any(feature = "log", feature = "tracing"))]
-            log_level: None,
-        }
+    fn skip_error(self) -> SkipErrorIter<R::IntoIter, T, E> {
+        SkipErrorIter::new(self.into_iter(), Skipper::new())
     }
     #[cfg(all(feature = "log", not(feature = "tracing")))]
-    fn skip_error_and_log<L>(self, log_level: L) -> SkipErrorIter<I, T, E>
+    fn skip_error_and_log<L>(self, log_level: L) -> SkipErrorIter<R::IntoIter, T, E>
     where
         L: Into<log::Level>,
     {
-        SkipErrorIter {
-            inner: self,
-            log_level: Some(log_level.into()),
-        }
+        SkipErrorIter::new(self.into_iter(), Skipper::with_log_level(log_level.into()))
     }
     #[cfg(feature = "tracing")]
-    fn skip_error_and_log<L>(self, log_level: L) -> SkipErrorIter<I, T, E>
+    fn skip_error_and_log<L>(self, log_level: L) -> SkipErrorIter<R::IntoIter, T, E>
     where
         L: Into<tracing::Level>,
     {
-        SkipErrorIter {
-            inner: self,
-            log_level: Some(log_level.into()),
+        SkipErrorIter::new(self.into_iter(), Skipper::with_log_level(log_level.into()))
+    }
+    #[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+    fn skip_error_and_log<L>(self, log_level: L) -> SkipErrorIter<R::IntoIter, T, E>
+    where
+        L: Into<DefmtLevel>,
+    {
+        SkipErrorIter::new(self.into_iter(), Skipper::with_log_level(log_level.into()))
+    }
+    fn skip_error_and_handle<F>(self, handler: F) -> HandleErrorIter<R::IntoIter, F>
+    where
+        F: FnMut(E) -> core::ops::ControlFlow<()>,
+    {
+        HandleErrorIter::new(self.into_iter(), handler)
+    }
+    fn skip_error_while<P>(self, predicate: P) -> SkipErrorWhile<R::IntoIter, P>
+    where
+        P: FnMut(&SkipReport) -> bool,
+    {
+        SkipErrorWhile::new(self.into_iter(), predicate)
+    }
+    fn recover_or_skip<F>(self, recover: F) -> RecoverOrSkip<R::IntoIter, F>
+    where
+        F: FnMut(&E) -> Option<T>,
+    {
+        RecoverOrSkip::new(self.into_iter(), recover, Skipper::new())
+    }
+    fn skip_skippable(self) -> SkipSkippable<R::IntoIter> {
+        SkipSkippable::new(self.into_iter())
+    }
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn recover_or_skip_and_log<F, L>(
+        self,
+        recover: F,
+        log_level: L,
+    ) -> RecoverOrSkip<R::IntoIter, F>
+    where
+        F: FnMut(&E) -> Option<T>,
+        L: Into<Level>,
+    {
+        RecoverOrSkip::new(
+            self.into_iter(),
+            recover,
+            Skipper::with_log_level(log_level.into()),
+        )
+    }
+    fn skip_error_into<B>(self, buffer: &mut B) -> SkipErrorInto<'_, R::IntoIter, B>
+    where
+        B: ErrorBuffer<E>,
+    {
+        SkipErrorInto::new(self.into_iter(), buffer)
+    }
+    #[cfg(feature = "alloc")]
+    fn skip_error_boxed<'a>(self) -> alloc::boxed::Box<dyn Iterator<Item = T> + Send + 'a>
+    where
+        R::IntoIter: Send + 'a,
+        T: Send + 'a,
+        E: core::fmt::Display + 'a,
+    {
+        alloc::boxed::Box::new(SkipErrorIter::new(self.into_iter(), Skipper::new()))
+    }
+    #[cfg(all(
+        feature = "alloc",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ))]
+    fn skip_error_boxed_and_log<'a, L>(
+        self,
+        log_level: L,
+    ) -> alloc::boxed::Box<dyn Iterator<Item = T> + Send + 'a>
+    where
+        R::IntoIter: Send + 'a,
+        T: Send + 'a,
+        E: core::fmt::Display + 'a,
+        L: Into<Level>,
+    {
+        alloc::boxed::Box::new(SkipErrorIter::new(
+            self.into_iter(),
+            Skipper::with_log_level(log_level.into()),
+        ))
+    }
+    #[cfg(feature = "alloc")]
+    fn try_collect_accumulating<C>(self) -> Result<C, alloc::vec::Vec<E>>
+    where
+        C: core::iter::FromIterator<T>,
+    {
+        // The errors are returned, so they are not recorded as skipped
+        let mut errors = alloc::vec::Vec::new();
+        let collection = self
+            .into_iter()
+            .filter_map(|result| match result {
+                Ok(value) => Some(value),
+                Err(error) => {
+                    errors.push(error);
+                    None
+                }
+            })
+            .collect();
+        match errors.is_empty() {
+            true => Ok(collection),
+            false => Err(errors),
         }
     }
 }
+
+/// Creates an iterator from a fallible generator closure, skipping all errors.
+///
+/// This mirrors [`std::iter::from_fn()`]: the closure is called on each
+/// iteration and the iteration ends as soon as it returns `None`. The
+/// `Some(Err(_))` values are skipped, only the `Some(Ok(_))` values are yielded.
+///
+/// ```edition2018
+/// let mut lines = vec!["1", "2", "three", "4"].into_iter();
+/// let numbers: Vec<u32> = skip_error::from_fallible_fn(|| {
+///   lines.next().map(|line| line.parse())
+/// })
+/// .collect();
+/// assert_eq!(numbers, vec![1, 2, 4]);
+/// ```
+#[cfg_attr(
+    feature = "forbid-silent",
+    deprecated(
+        note = "the errors must not be skipped silently, log them with `std::iter::from_fn(f).skip_error_and_log()`"
+    )
+)]
+pub fn from_fallible_fn<F, T, E>(f: F) -> SkipErrorIter<core::iter::FromFn<F>, T, E>
+where
+    F: FnMut() -> Option<Result<T, E>>,
+{
+    SkipErrorIter::new(core::iter::from_fn(f), Skipper::new())
+}
+
+/// Gives the `Ok()` values of `iter` to `f` as an infallible iterator,
+/// skipping the errors, and returns the result of `f` along with the
+/// [`SkipReport`] of the iteration, like `itertools::process_results()` but
+/// going on after the errors.
+///
+/// The report only counts the items consumed by `f`.
+///
+/// ```edition2018
+/// let (sum, report) = skip_error::process_skipping(
+///   vec!["1", "two", "3"].into_iter().map(str::parse::<u32>),
+///   |numbers| numbers.sum::<u32>(),
+/// );
+/// assert_eq!(sum, 4);
+/// assert_eq!(report.skipped(), 1);
+/// ```
+#[cfg_attr(
+    feature = "forbid-silent",
+    deprecated(
+        note = "the errors must not be skipped silently, log them with `process_skipping_and_log()`"
+    )
+)]
+pub fn process_skipping<I, T, E, F, R>(iter: I, f: F) -> (R, SkipReport)
+where
+    I: IntoIterator<Item = Result<T, E>>,
+    E: core::fmt::Display,
+    F: FnOnce(&mut SkipErrorIter<I::IntoIter, T, E>) -> R,
+{
+    let mut values = SkipErrorIter::new(iter.into_iter(), Skipper::new());
+    let result = f(&mut values);
+    (result, values.report())
+}
+
+/// Like [`process_skipping()`], but logs the skipped errors at `log_level`.
+///
+/// ```edition2018
+/// # testing_logger::setup();
+/// let (max, report) = skip_error::process_skipping_and_log(
+///   vec!["1", "two", "3"].into_iter().map(str::parse::<u32>),
+#[cfg_attr(
+    all(feature = "log", not(feature = "tracing")),
+    doc = "  log::Level::Warn,"
+)]
+#[cfg_attr(feature = "tracing", doc = "  tracing::Level::WARN,")]
+///   |numbers| numbers.max(),
+/// );
+/// assert_eq!(max, Some(3));
+/// assert_eq!(report.skipped(), 1);
+/// testing_logger::validate(|captured_logs| {
+///   assert_eq!(captured_logs[0].body, "invalid digit found in string");
+/// });
+/// ```
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+pub fn process_skipping_and_log<I, T, E, L, F, R>(iter: I, log_level: L, f: F) -> (R, SkipReport)
+where
+    I: IntoIterator<Item = Result<T, E>>,
+    E: core::fmt::Display,
+    L: Into<Level>,
+    F: FnOnce(&mut SkipErrorIter<I::IntoIter, T, E>) -> R,
+{
+    let mut values =
+        SkipErrorIter::new(iter.into_iter(), Skipper::with_log_level(log_level.into()));
+    let result = f(&mut values);
+    (result, values.report())
+}
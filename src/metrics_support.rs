@@ -0,0 +1,5 @@
+//! Counting the skipped errors with the [`metrics`](::metrics) facade.
+
+pub(crate) fn record(target: &'static str) {
+    metrics::counter!("skip_error.skipped", "target" => target).increment(1);
+}
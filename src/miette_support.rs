@@ -0,0 +1,71 @@
+//! Rendering of the [`miette::Diagnostic`] errors with a report handler.
+use miette::{Diagnostic, NarratableReportHandler};
+use std::fmt;
+
+/// A [`miette::Diagnostic`] displayed with the
+/// [`miette::NarratableReportHandler`], with its code, labels and help text,
+/// instead of its flat [`std::fmt::Display`].
+///
+/// The narratable rendering is plain text, so it fits in the logs. The errors
+/// skipped by the macros and the adapters are rendered with
+/// [`std::fmt::Display`], so wrap the errors into [`MietteDiagnostic`] to keep
+/// their diagnostics.
+///
+/// ```edition2018
+/// use skip_error::{MietteDiagnostic, SkipError};
+/// use std::fmt;
+///
+/// #[derive(Debug, miette::Diagnostic)]
+/// #[diagnostic(code(import::number), help("use digits only"))]
+/// struct InvalidNumber(String);
+/// impl fmt::Display for InvalidNumber {
+///   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///     write!(f, "invalid number {:?}", self.0)
+///   }
+/// }
+/// impl std::error::Error for InvalidNumber {}
+///
+/// # testing_logger::setup();
+/// let numbers: Vec<u32> = ["1", "two", "3"]
+///   .iter()
+///   .map(|string_number| {
+///     string_number
+///       .parse()
+///       .map_err(|_| MietteDiagnostic(InvalidNumber(string_number.to_string())))
+///   })
+#[cfg_attr(
+    all(feature = "log", not(feature = "tracing")),
+    doc = "  .skip_error_and_log(log::Level::Warn)"
+)]
+#[cfg_attr(
+    feature = "tracing",
+    doc = "  .skip_error_and_log(tracing::Level::WARN)"
+)]
+#[cfg_attr(
+    not(any(feature = "log", feature = "tracing")),
+    doc = "  .skip_error()"
+)]
+///   .collect();
+/// assert_eq!(numbers, vec![1, 3]);
+#[cfg_attr(
+    any(feature = "log", feature = "tracing"),
+    doc = r##"
+testing_logger::validate(|captured_logs| {
+  assert!(captured_logs[0].body.starts_with("invalid number \"two\""));
+  assert!(captured_logs[0].body.contains("diagnostic code: import::number"));
+  assert!(captured_logs[0].body.contains("use digits only"));
+});
+"##
+)]
+/// ```
+#[derive(Debug)]
+pub struct MietteDiagnostic<D>(pub D);
+
+impl<D> fmt::Display for MietteDiagnostic<D>
+where
+    D: Diagnostic,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        NarratableReportHandler::new().render_report(f, &self.0)
+    }
+}
@@ -0,0 +1,110 @@
+//! Polling of the non-blocking operations of [`nb`](https://docs.rs/nb).
+
+/// An iterator retrying the operations which would block, and yielding the
+/// other results.
+///
+/// This `struct` is created by [`SkipWouldBlock::skip_would_block()`].
+pub struct SkipWouldBlockIter<I, F> {
+    inner: I,
+    hook: F,
+    would_block: usize,
+}
+
+impl<I, F> SkipWouldBlockIter<I, F> {
+    /// Calls `hook` each time the operation would block, before polling it
+    /// again, for example to spin with [`core::hint::spin_loop()`] or to wait
+    /// for an interrupt.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipWouldBlock;
+    /// let mut polls = vec![Err(nb::Error::WouldBlock), Ok(42)].into_iter();
+    /// let mut spins = 0;
+    /// let sample: Result<u32, ()> = core::iter::from_fn(|| polls.next())
+    ///   .skip_would_block()
+    ///   .on_would_block(|| spins += 1)
+    ///   .next()
+    ///   .unwrap();
+    /// assert_eq!(sample, Ok(42));
+    /// assert_eq!(spins, 1);
+    /// ```
+    pub fn on_would_block<G>(self, hook: G) -> SkipWouldBlockIter<I, G>
+    where
+        G: FnMut(),
+    {
+        SkipWouldBlockIter {
+            inner: self.inner,
+            hook,
+            would_block: self.would_block,
+        }
+    }
+
+    /// The number of polls which would have blocked so far.
+    pub fn would_block(&self) -> usize {
+        self.would_block
+    }
+}
+
+impl<I, F, T, E> Iterator for SkipWouldBlockIter<I, F>
+where
+    I: Iterator<Item = nb::Result<T, E>>,
+    F: FnMut(),
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.inner.next()? {
+                Ok(value) => return Some(Ok(value)),
+                Err(nb::Error::Other(error)) => return Some(Err(error)),
+                Err(nb::Error::WouldBlock) => {
+                    self.would_block += 1;
+                    (self.hook)();
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+fn no_hook() {}
+
+/// Trait to extend any [`Iterator`] of [`nb::Result`] with an adapter polling
+/// again the operations which would block, and yielding the real failures
+/// `nb::Error::Other` as errors.
+///
+/// The errors can then be propagated, or skipped and logged with
+/// [`SkipError`](crate::SkipError), so the sensor-polling loops never give up
+/// on a sample for a `nb::Error::WouldBlock`.
+///
+/// ```edition2018
+/// use skip_error::{SkipError, SkipWouldBlock};
+/// let mut polls = vec![
+///   Ok(1),
+///   Err(nb::Error::WouldBlock),
+///   Err(nb::Error::Other("checksum mismatch")),
+///   Err(nb::Error::WouldBlock),
+///   Ok(2),
+/// ]
+/// .into_iter();
+/// let samples: Vec<u32> = core::iter::from_fn(|| polls.next())
+///   .skip_would_block()
+///   .skip_error()
+///   .collect();
+/// assert_eq!(samples, vec![1, 2]);
+/// ```
+pub trait SkipWouldBlock<T, E>: Iterator<Item = nb::Result<T, E>> + Sized {
+    /// Returns an iterator polling again, without waiting, while the
+    /// operation would block.
+    fn skip_would_block(self) -> SkipWouldBlockIter<Self, fn()> {
+        SkipWouldBlockIter {
+            inner: self,
+            hook: no_hook,
+            would_block: 0,
+        }
+    }
+}
+
+impl<I, T, E> SkipWouldBlock<T, E> for I where I: Iterator<Item = nb::Result<T, E>> {}
@@ -0,0 +1,127 @@
+//! Skipping the transient errors when accepting TCP connections.
+use crate::skipper::Skipper;
+use std::{
+    io,
+    net::{Incoming, TcpListener, TcpStream},
+    time::Duration,
+};
+
+// Errors after which the next connections can still be accepted
+fn is_transient_accept_error(error: &io::Error) -> bool {
+    match error.kind() {
+        io::ErrorKind::ConnectionAborted
+        | io::ErrorKind::ConnectionReset
+        | io::ErrorKind::Interrupted
+        | io::ErrorKind::TimedOut => true,
+        // Too many open files, by the process (EMFILE) or the system (ENFILE)
+        #[cfg(unix)]
+        _ if matches!(error.raw_os_error(), Some(23) | Some(24)) => true,
+        _ => false,
+    }
+}
+
+/// An iterator over the connections of a [`TcpListener`], skipping the
+/// transient errors.
+///
+/// This `struct` is created by
+/// [`SkipErrorTcpListenerExt::incoming_skip_transient()`].
+pub struct IncomingSkipTransient<'a> {
+    inner: Incoming<'a>,
+    skipper: Skipper,
+    sleep: Option<Duration>,
+}
+
+impl IncomingSkipTransient<'_> {
+    skip_adapter_methods!();
+    budget_adapter_methods!();
+
+    /// Sleeps for `duration` after each transient error, to let the cause
+    /// (like too many open files) vanish before accepting again.
+    pub fn sleep_on_error(mut self, duration: Duration) -> Self {
+        self.sleep = Some(duration);
+        self
+    }
+}
+
+impl Iterator for IncomingSkipTransient<'_> {
+    type Item = io::Result<TcpStream>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.skipper.exhausted() {
+            match self.inner.next()? {
+                Ok(stream) => {
+                    self.skipper.ok();
+                    return Some(Ok(stream));
+                }
+                Err(error) if is_transient_accept_error(&error) => {
+                    self.skipper.skip(&error);
+                    if let Some(duration) = self.sleep {
+                        std::thread::sleep(duration);
+                    }
+                }
+                Err(error) => return Some(Err(error)),
+            }
+        }
+        None
+    }
+}
+
+/// Trait to extend [`TcpListener`] with an accept loop which survives the
+/// transient errors: an aborted or reset connection, an interruption, a
+/// timeout, or too many open files.
+///
+/// The other errors are still yielded, so they can be handled.
+///
+/// ```edition2018
+/// use skip_error::SkipErrorTcpListenerExt;
+/// use std::net::{TcpListener, TcpStream};
+/// use std::time::Duration;
+/// let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+/// let address = listener.local_addr().unwrap();
+/// std::thread::spawn(move || TcpStream::connect(address).unwrap());
+/// let mut incoming = listener
+///   .incoming_skip_transient()
+///   .sleep_on_error(Duration::from_millis(100));
+/// let stream = incoming.next().unwrap().unwrap();
+/// assert_eq!(stream.local_addr().unwrap(), address);
+/// assert_eq!(incoming.report().skipped(), 0);
+/// ```
+pub trait SkipErrorTcpListenerExt {
+    /// Returns an iterator over the connections, skipping the transient errors.
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `incoming_skip_transient_and_log()`"
+        )
+    )]
+    fn incoming_skip_transient(&self) -> IncomingSkipTransient<'_>;
+
+    /// Returns an iterator over the connections, logging and skipping the
+    /// transient errors.
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn incoming_skip_transient_and_log<L>(&self, log_level: L) -> IncomingSkipTransient<'_>
+    where
+        L: Into<crate::Level>;
+}
+
+impl SkipErrorTcpListenerExt for TcpListener {
+    fn incoming_skip_transient(&self) -> IncomingSkipTransient<'_> {
+        IncomingSkipTransient {
+            inner: self.incoming(),
+            skipper: Skipper::new(),
+            sleep: None,
+        }
+    }
+
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn incoming_skip_transient_and_log<L>(&self, log_level: L) -> IncomingSkipTransient<'_>
+    where
+        L: Into<crate::Level>,
+    {
+        IncomingSkipTransient {
+            inner: self.incoming(),
+            skipper: Skipper::with_log_level(log_level.into()),
+            sleep: None,
+        }
+    }
+}
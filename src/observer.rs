@@ -0,0 +1,142 @@
+//! Process-wide observers of the skipped errors.
+use crate::SkippedError;
+use core::panic::Location;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, RwLock,
+};
+
+static OBSERVERS: RwLock<Vec<(ObserverId, Arc<dyn SkipObserver>)>> = RwLock::new(Vec::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A skipped error given to the observers, with where and how it was skipped.
+pub struct SkipEvent<'a> {
+    skipped: SkippedError<'a>,
+    location: Option<&'static Location<'static>>,
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    level: Option<crate::Level>,
+}
+
+impl<'a> SkipEvent<'a> {
+    pub(crate) fn new(
+        skipped: SkippedError<'a>,
+        location: Option<&'static Location<'static>>,
+    ) -> Self {
+        SkipEvent {
+            skipped,
+            location,
+            #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+            level: None,
+        }
+    }
+
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    pub(crate) fn with_level(self, level: Option<crate::Level>) -> Self {
+        SkipEvent { level, ..self }
+    }
+
+    /// The skipped error.
+    pub fn skipped(&self) -> &SkippedError<'a> {
+        &self.skipped
+    }
+
+    /// Where the error was skipped, if it was skipped by one of the macros
+    /// (the adapters don't know where they were created).
+    pub fn location(&self) -> Option<&'static Location<'static>> {
+        self.location
+    }
+
+    /// The name of the adapter which skipped the error, if it was given one
+    /// with `named()`.
+    pub fn adapter(&self) -> Option<&'a str> {
+        self.skipped.name()
+    }
+
+    /// The level at which the error was logged, if it was.
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    pub fn level(&self) -> Option<crate::Level> {
+        self.level
+    }
+}
+
+/// An observer receiving every error skipped in the process, either with one
+/// of the macros or with one of the adapters, once added with
+/// [`add_observer()`].
+///
+/// It is implemented for any closure `Fn(&SkipEvent<'_>)`.
+pub trait SkipObserver: Send + Sync {
+    /// Called for each skipped error, on the thread where it was skipped.
+    fn observe(&self, event: &SkipEvent<'_>);
+}
+
+impl<F> SkipObserver for F
+where
+    F: Fn(&SkipEvent<'_>) + Send + Sync,
+{
+    fn observe(&self, event: &SkipEvent<'_>) {
+        self(event)
+    }
+}
+
+/// The identifier of an observer, to remove it with [`remove_observer()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(u64);
+
+/// Adds `observer`, which receives all the errors skipped from now on, along
+/// with the other observers.
+///
+/// ```edition2018
+/// # #[macro_use]
+/// # extern crate skip_error;
+/// use std::sync::{Arc, Mutex};
+/// # fn main() {
+/// let lines = Arc::new(Mutex::new(Vec::new()));
+/// let audit = Arc::clone(&lines);
+/// let id = skip_error::add_observer(move |event: &skip_error::SkipEvent<'_>| {
+///   let line = event.location().map(|location| location.line());
+///   audit.lock().unwrap().push((event.skipped().error().to_string(), line));
+/// });
+/// for string_number in &["1", "two", "3"] {
+///   let number: u32 = skip_error!(string_number.parse());
+/// }
+/// assert!(skip_error::remove_observer(id));
+/// let lines = lines.lock().unwrap();
+/// assert_eq!(lines[0].0, "invalid digit found in string");
+/// assert!(lines[0].1.is_some());
+/// # }
+/// ```
+pub fn add_observer<O>(observer: O) -> ObserverId
+where
+    O: SkipObserver + 'static,
+{
+    let id = ObserverId(NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    OBSERVERS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .push((id, Arc::new(observer)));
+    id
+}
+
+/// Removes the observer `id`, returning whether it was still added.
+pub fn remove_observer(id: ObserverId) -> bool {
+    let mut observers = OBSERVERS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let len = observers.len();
+    observers.retain(|(observer_id, _)| *observer_id != id);
+    observers.len() != len
+}
+
+pub(crate) fn notify(event: &SkipEvent<'_>) {
+    // The observers are called without the lock, so they can add or remove
+    // observers themselves
+    let observers: Vec<Arc<dyn SkipObserver>> = OBSERVERS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .iter()
+        .map(|(_, observer)| Arc::clone(observer))
+        .collect();
+    for observer in observers {
+        observer.observe(event);
+    }
+}
@@ -0,0 +1,17 @@
+//! Recording the skipped errors in the active
+//! [`opentelemetry`](::opentelemetry) span.
+use opentelemetry::{trace::get_active_span, KeyValue};
+
+pub(crate) fn record(error: &dyn std::fmt::Display, error_type: &'static str) {
+    get_active_span(|span| {
+        if span.is_recording() {
+            span.add_event(
+                "skipped_error",
+                vec![
+                    KeyValue::new("exception.type", error_type),
+                    KeyValue::new("exception.message", error.to_string()),
+                ],
+            );
+        }
+    });
+}
@@ -0,0 +1,99 @@
+//! Exit status of a program depending on the skipped errors.
+use crate::{SkipError, SkipReport};
+use std::process::{ExitCode, Termination};
+
+/// The outcome of a lenient program, which can be returned by `main()`: the
+/// program then prints a summary and exits with a failure if any error was
+/// skipped.
+///
+/// This `struct` is created by [`run_skipping()`], or from the
+/// [`SkipReport`] of any adapter.
+///
+/// ```edition2018,no_run
+/// use skip_error::{SkipError, SkipOutcome};
+/// fn main() -> SkipOutcome {
+///   let mut numbers = ["1", "two", "3"]
+///     .iter()
+///     .map(|string_number| string_number.parse::<u32>())
+///     .skip_error();
+///   numbers.by_ref().for_each(|number| println!("{}", number));
+///   // Prints "2 values processed, 1 error skipped" and exits with a failure
+///   SkipOutcome::from(numbers.report())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkipOutcome {
+    report: SkipReport,
+}
+
+impl SkipOutcome {
+    /// The [`SkipReport`] of the program.
+    pub fn report(&self) -> SkipReport {
+        self.report
+    }
+
+    /// Whether no error was skipped.
+    pub fn is_success(&self) -> bool {
+        self.report.skipped() == 0
+    }
+}
+
+impl From<SkipReport> for SkipOutcome {
+    fn from(report: SkipReport) -> Self {
+        SkipOutcome { report }
+    }
+}
+
+impl Termination for SkipOutcome {
+    fn report(self) -> ExitCode {
+        let values = match self.report.ok() {
+            1 => "value",
+            _ => "values",
+        };
+        let errors = match self.report.skipped() {
+            1 => "error",
+            _ => "errors",
+        };
+        eprintln!(
+            "{} {} processed, {} {} skipped",
+            self.report.ok(),
+            values,
+            self.report.skipped(),
+            errors
+        );
+        match self.is_success() {
+            true => ExitCode::SUCCESS,
+            false => ExitCode::FAILURE,
+        }
+    }
+}
+
+/// Calls `f` on each `Ok()` value of `iter`, skipping the errors, and returns
+/// the [`SkipOutcome`] of the whole iteration.
+///
+/// ```edition2018
+/// let mut sum = 0;
+/// let outcome = skip_error::run_skipping(vec!["1", "two", "3"].into_iter().map(str::parse::<u32>), |number| {
+///   sum += number;
+/// });
+/// assert_eq!(sum, 4);
+/// assert_eq!(outcome.report().skipped(), 1);
+/// assert!(!outcome.is_success());
+/// ```
+#[cfg_attr(
+    feature = "forbid-silent",
+    deprecated(
+        note = "the errors must not be skipped silently, log them with `skip_error_and_log()` and `SkipOutcome::from()`"
+    )
+)]
+#[allow(deprecated)]
+pub fn run_skipping<I, T, E, F>(iter: I, f: F) -> SkipOutcome
+where
+    I: IntoIterator<Item = Result<T, E>>,
+    E: std::fmt::Display,
+    F: FnMut(T),
+{
+    let mut values = iter.into_iter().skip_error();
+    values.by_ref().for_each(f);
+    SkipOutcome::from(values.report())
+}
@@ -0,0 +1,120 @@
+//! Conversion of the errors which can't be sent to another thread.
+use crate::OwnedSkippedError;
+use alloc::string::ToString;
+
+/// An iterator converting the errors of the original iterator.
+///
+/// This `struct` is created by [`SendErrorsExt::send_errors_as()`].
+pub struct SendErrorsAs<I, F> {
+    inner: I,
+    convert: F,
+}
+
+impl<I, F, T, E, O> Iterator for SendErrorsAs<I, F>
+where
+    I: Iterator<Item = Result<T, E>>,
+    F: FnMut(E) -> O,
+{
+    type Item = Result<T, O>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|result| result.map_err(&mut self.convert))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// An iterator rendering the errors of the original iterator into
+/// [`OwnedSkippedError`]s.
+///
+/// This `struct` is created by [`SendErrorsExt::errors_into_owned()`].
+pub struct OwnedErrors<I> {
+    inner: I,
+    index: usize,
+}
+
+impl<I, T, E> Iterator for OwnedErrors<I>
+where
+    I: Iterator<Item = Result<T, E>>,
+    E: core::fmt::Display,
+{
+    type Item = Result<T, OwnedSkippedError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.inner.next()?;
+        let index = self.index;
+        self.index += 1;
+        Some(result.map_err(|error| {
+            OwnedSkippedError::new(error.to_string(), core::any::type_name::<E>(), Some(index))
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Trait to extend any [`Iterator`] of [`Result`] with conversions of the
+/// errors, so the errors which are not [`Send`] (holding an
+/// [`Rc`](alloc::rc::Rc) or a raw pointer, for example) can still be reported
+/// across threads, in a degraded but owned form.
+///
+/// ```edition2018
+/// use skip_error::{SendErrorsExt, SkipError};
+/// use std::{fmt, rc::Rc};
+///
+/// struct SharedError(Rc<String>);
+/// impl fmt::Display for SharedError {
+///   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///     write!(f, "{}", self.0)
+///   }
+/// }
+///
+/// let results = vec![Ok(1), Err(SharedError(Rc::new("boom".to_string()))), Ok(3)];
+/// let results: Vec<_> = results.into_iter().errors_into_owned().collect();
+/// let numbers: Vec<u32> = std::thread::spawn(move || results.skip_error().collect())
+///   .join()
+///   .unwrap();
+/// assert_eq!(numbers, vec![1, 3]);
+/// ```
+pub trait SendErrorsExt<T, E>: Iterator<Item = Result<T, E>> + Sized {
+    /// Converts each error with `convert`, like with
+    /// `.map(|result| result.map_err(convert))`.
+    ///
+    /// ```edition2018
+    /// use skip_error::SendErrorsExt;
+    /// let messages: Vec<Result<u32, String>> = vec![Ok(1), Err(std::rc::Rc::new("boom"))]
+    ///   .into_iter()
+    ///   .send_errors_as(|error| error.to_string())
+    ///   .collect();
+    /// assert_eq!(messages, vec![Ok(1), Err("boom".to_string())]);
+    /// ```
+    fn send_errors_as<F, O>(self, convert: F) -> SendErrorsAs<Self, F>
+    where
+        F: FnMut(E) -> O,
+    {
+        SendErrorsAs {
+            inner: self,
+            convert,
+        }
+    }
+
+    /// Renders each error into an [`OwnedSkippedError`], keeping the name of
+    /// its type and its position in the original iterator.
+    fn errors_into_owned(self) -> OwnedErrors<Self>
+    where
+        E: core::fmt::Display,
+    {
+        OwnedErrors {
+            inner: self,
+            index: 0,
+        }
+    }
+}
+
+impl<I, T, E> SendErrorsExt<T, E> for I where I: Iterator<Item = Result<T, E>> {}
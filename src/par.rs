@@ -0,0 +1,140 @@
+//! Parallel maps on scoped threads, skipping the errors.
+use crate::{skipper::Skipper, SkipErrorIter};
+use std::{
+    sync::{mpsc, Mutex},
+    thread,
+};
+
+/// The iterator over the results of [`ParMapSkipError::par_map_skip_error()`],
+/// in the order of the original iterator.
+pub type ParMapResults<T, E> = SkipErrorIter<std::vec::IntoIter<Result<T, E>>, T, E>;
+
+/// Trait to extend any [`Iterator`] with a fallible function applied in
+/// parallel, on scoped threads. This is a lightweight alternative to the
+/// `rayon` feature.
+pub trait ParMapSkipError: Iterator + Sized {
+    /// Applies `f` on each item on `num_threads` threads (at least one), and
+    /// returns an iterator over the `Ok()` values, in the order of the
+    /// original iterator, skipping the `Err()` values.
+    ///
+    /// All the items are processed before this function returns; the errors
+    /// are skipped (and logged, see
+    /// [`ParMapSkipError::par_map_skip_error_and_log()`]) while iterating over
+    /// the results, so [`SkipErrorIter::report()`] is available as usual.
+    ///
+    /// ```edition2018
+    /// use skip_error::ParMapSkipError;
+    /// let mut numbers = vec!["1", "2", "three", "4"]
+    ///   .into_iter()
+    ///   .par_map_skip_error(2, |string_number| string_number.parse::<u32>());
+    /// let collected: Vec<u32> = numbers.by_ref().collect();
+    /// assert_eq!(collected, vec![1, 2, 4]);
+    /// assert_eq!(numbers.report().skipped(), 1);
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `par_map_skip_error_and_log()`"
+        )
+    )]
+    fn par_map_skip_error<F, T, E>(self, num_threads: usize, f: F) -> ParMapResults<T, E>
+    where
+        Self::Item: Send,
+        F: Fn(Self::Item) -> Result<T, E> + Sync,
+        T: Send,
+        E: Send,
+    {
+        SkipErrorIter::new(par_map(self, num_threads, f).into_iter(), Skipper::new())
+    }
+
+    /// Applies `f` on each item in parallel like
+    /// [`ParMapSkipError::par_map_skip_error()`], and logs the skipped errors
+    /// (see
+    /// [`SkipError::skip_error_and_log()`](crate::SkipError::skip_error_and_log)).
+    ///
+    /// ```edition2018
+    /// use skip_error::ParMapSkipError;
+    /// # testing_logger::setup();
+    /// let numbers: Vec<u32> = vec!["1", "2", "three", "4"]
+    ///   .into_iter()
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .par_map_skip_error_and_log(2, log::Level::Warn, |string_number| string_number.parse())"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .par_map_skip_error_and_log(2, tracing::Level::WARN, |string_number| string_number.parse())"
+    )]
+    ///   .collect();
+    /// assert_eq!(numbers, vec![1, 2, 4]);
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs.len(), 1);
+    ///   assert_eq!(captured_logs[0].body, "invalid digit found in string");
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn par_map_skip_error_and_log<L, F, T, E>(
+        self,
+        num_threads: usize,
+        log_level: L,
+        f: F,
+    ) -> ParMapResults<T, E>
+    where
+        L: Into<crate::Level>,
+        Self::Item: Send,
+        F: Fn(Self::Item) -> Result<T, E> + Sync,
+        T: Send,
+        E: std::fmt::Display + Send,
+    {
+        SkipErrorIter::new(
+            par_map(self, num_threads, f).into_iter(),
+            Skipper::with_log_level(log_level.into()),
+        )
+    }
+}
+
+impl<I> ParMapSkipError for I where I: Iterator {}
+
+// Feeds the items to the threads from the current thread, so the iterator
+// does not need to be `Send`, then restores the original order.
+fn par_map<I, F, T, E>(items: I, num_threads: usize, f: F) -> Vec<Result<T, E>>
+where
+    I: Iterator,
+    I::Item: Send,
+    F: Fn(I::Item) -> Result<T, E> + Sync,
+    T: Send,
+    E: Send,
+{
+    let num_threads = num_threads.max(1);
+    let (item_sender, item_receiver) = mpsc::sync_channel(num_threads);
+    let item_receiver = Mutex::new(item_receiver);
+    let (result_sender, result_receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..num_threads {
+            let result_sender = result_sender.clone();
+            let (item_receiver, f) = (&item_receiver, &f);
+            scope.spawn(move || loop {
+                let item = item_receiver
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .recv();
+                match item {
+                    Ok((index, item)) => {
+                        let _ = result_sender.send((index, f(item)));
+                    }
+                    Err(_) => break,
+                }
+            });
+        }
+        for item in items.enumerate() {
+            if item_sender.send(item).is_err() {
+                break;
+            }
+        }
+        drop(item_sender);
+    });
+    drop(result_sender);
+    let mut results: Vec<(usize, Result<T, E>)> = result_receiver.into_iter().collect();
+    results.sort_unstable_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
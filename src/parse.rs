@@ -0,0 +1,88 @@
+//! Parsing the strings of an iterator, skipping the invalid ones.
+use crate::{SkipError, SkipErrorIter};
+use core::iter::Map;
+use core::str::FromStr;
+
+fn parse<S, T>(string: S) -> Result<T, T::Err>
+where
+    S: AsRef<str>,
+    T: FromStr,
+{
+    string.as_ref().parse()
+}
+
+/// Trait to extend any [`Iterator`] of strings with an iterator of the values
+/// parsed with [`FromStr`], skipping the strings which could not be parsed.
+pub trait SkipErrorParseExt: Iterator + Sized
+where
+    Self::Item: AsRef<str>,
+{
+    /// Returns an iterator parsing each string, skipping the errors.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipErrorParseExt;
+    /// let numbers: Vec<u32> = ["1", "2", "three", "4"].iter().parse_skipping::<u32>().collect();
+    /// assert_eq!(numbers, vec![1, 2, 4]);
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `parse_skipping_and_log()`"
+        )
+    )]
+    #[allow(deprecated)]
+    #[allow(clippy::type_complexity)]
+    fn parse_skipping<T>(
+        self,
+    ) -> SkipErrorIter<Map<Self, fn(Self::Item) -> Result<T, T::Err>>, T, T::Err>
+    where
+        T: FromStr,
+    {
+        self.map(parse as fn(Self::Item) -> Result<T, T::Err>)
+            .skip_error()
+    }
+
+    /// Returns an iterator parsing each string, logging and skipping the
+    /// errors (see [`SkipError::skip_error_and_log()`]).
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipErrorParseExt;
+    /// # testing_logger::setup();
+    /// let numbers: Vec<u32> = "1\n2\nthree\n4"
+    ///   .lines()
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .parse_skipping_and_log::<u32, _>(log::Level::Warn)"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .parse_skipping_and_log::<u32, _>(tracing::Level::WARN)"
+    )]
+    ///   .collect();
+    /// assert_eq!(numbers, vec![1, 2, 4]);
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs[0].level, log::Level::Warn);
+    ///   assert_eq!(captured_logs[0].body, "invalid digit found in string");
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    #[allow(clippy::type_complexity)]
+    fn parse_skipping_and_log<T, L>(
+        self,
+        log_level: L,
+    ) -> SkipErrorIter<Map<Self, fn(Self::Item) -> Result<T, T::Err>>, T, T::Err>
+    where
+        T: FromStr,
+        L: Into<crate::Level>,
+    {
+        self.map(parse as fn(Self::Item) -> Result<T, T::Err>)
+            .skip_error_and_log(log_level.into())
+    }
+}
+
+impl<I> SkipErrorParseExt for I
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+}
@@ -0,0 +1,49 @@
+//! Counting the skipped errors with [`prometheus`](::prometheus).
+use prometheus::{IntCounterVec, Opts};
+use std::sync::OnceLock;
+
+static COUNTER: OnceLock<IntCounterVec> = OnceLock::new();
+
+/// Returns the counter of the skipped errors, `skip_error_skipped_total`,
+/// labelled by `target` (the module where the error was skipped, the same as
+/// the target of the logs) and `error_type` (the name of the type of the
+/// error).
+///
+/// The counter is incremented by the macros and the adapters, but it is not
+/// registered in any [`prometheus::Registry`]: register it in the registry
+/// which is exported.
+///
+/// ```edition2018
+/// use skip_error::SkipError;
+/// let registry = prometheus::Registry::new();
+/// registry.register(Box::new(skip_error::prometheus_counter())).unwrap();
+/// let numbers: Vec<u32> = ["1", "two", "3", "four"]
+///   .iter()
+///   .map(|string_number| string_number.parse())
+///   .skip_error()
+///   .collect();
+/// let families = registry.gather();
+/// assert_eq!(families[0].name(), "skip_error_skipped_total");
+/// let metric = &families[0].get_metric()[0];
+/// assert_eq!(metric.get_counter().get_value(), 2.0);
+/// let error_type = metric.get_label().iter().find(|label| label.name() == "error_type").unwrap();
+/// assert_eq!(error_type.value(), "core::num::error::ParseIntError");
+/// ```
+pub fn prometheus_counter() -> IntCounterVec {
+    COUNTER
+        .get_or_init(|| {
+            let opts = Opts::new(
+                "skip_error_skipped_total",
+                "Number of errors skipped by skip_error",
+            );
+            IntCounterVec::new(opts, &["target", "error_type"])
+                .expect("the options of the counter are valid")
+        })
+        .clone()
+}
+
+pub(crate) fn record(target: &'static str, error_type: &'static str) {
+    prometheus_counter()
+        .with_label_values(&[target, error_type])
+        .inc();
+}
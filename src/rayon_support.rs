@@ -0,0 +1,445 @@
+//! Support for the parallel iterators of [`rayon`](::rayon).
+//!
+//! Each job of the pool buffers the errors it skips, and hands them to the
+//! skipper of the adapter, shared by all the threads, only once its buffer is
+//! full or once it is done: the lock is taken once for many errors, even
+//! under heavy error rates, and the options of the sequential adapters, like
+//! the repeats collapsed or the sinks, apply to all the threads.
+use crate::skipper::Skipper;
+use crate::TooManyErrors;
+use rayon::iter::{plumbing::UnindexedConsumer, Either, ParallelExtend, ParallelIterator};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex, PoisonError,
+};
+
+// The number of errors buffered by a job before it takes the lock of the
+// shared skipper
+const BUFFER_SIZE: usize = 64;
+
+/// A parallel iterator that ignore errors
+///
+/// It is configured like the sequential adapters, but its
+/// [`report()`](ParSkipError::report) is only updated once it is driven:
+/// follow the iteration with [`report_handle()`](ParSkipError::report_handle).
+///
+/// ```edition2018
+/// use rayon::prelude::*;
+/// use skip_error::ParallelSkipError;
+/// let mut numbers = ["1", "two", "3", "four", "5"]
+///   .par_iter()
+///   .map(|string_number| string_number.parse::<u32>())
+///   .skip_error();
+/// let handle = numbers.report_handle();
+/// assert_eq!(numbers.count(), 3);
+/// assert_eq!((handle.report().ok(), handle.report().skipped()), (3, 2));
+/// ```
+///
+/// The errors of all the threads go through the same options, like
+/// `named()`, `collapse_repeated()` or the sinks.
+///
+/// ```edition2018
+/// use rayon::prelude::*;
+/// use skip_error::{ParallelSkipError, SkipSink, SkippedError};
+///
+/// struct Messages(std::sync::mpsc::Sender<String>);
+/// impl SkipSink for Messages {
+///   fn skipped(&mut self, skipped: &SkippedError<'_>) {
+///     let _ = self.0.send(format!("[{}] {}", skipped.name().unwrap(), skipped.error()));
+///   }
+/// }
+///
+/// let (sender, receiver) = std::sync::mpsc::channel();
+/// let numbers: Vec<u32> = (0..1000)
+///   .into_par_iter()
+///   .map(|n| if n % 100 == 0 { "?".parse() } else { Ok(n) })
+///   .skip_error()
+///   .named("import")
+///   .with_sink(Messages(sender))
+///   .collect();
+/// assert_eq!(numbers.len(), 990);
+/// let messages: Vec<String> = receiver.iter().collect();
+/// assert_eq!(messages.len(), 10);
+/// assert_eq!(messages[0], "[import] invalid digit found in string");
+/// ```
+///
+/// With `with_thread_name()`, each logged error is tagged with the name of the
+/// thread of the pool where it was skipped.
+#[cfg_attr(
+    all(feature = "log", not(feature = "tracing")),
+    doc = r##"
+```edition2018
+use rayon::prelude::*;
+use skip_error::ParallelSkipError;
+let pool = rayon::ThreadPoolBuilder::new()
+  .num_threads(1)
+  .thread_name(|index| format!("importer-{}", index))
+  .build()
+  .unwrap();
+pool.install(|| {
+  testing_logger::setup();
+  let numbers: Vec<u32> = vec!["1", "two", "3"]
+    .into_par_iter()
+    .map(|string_number| string_number.parse())
+    .skip_error_and_log(log::Level::Warn)
+    .with_thread_name()
+    .collect();
+  assert_eq!(numbers, vec![1, 3]);
+  testing_logger::validate(|captured_logs| {
+    assert_eq!(captured_logs[0].body, "thread 'importer-0': invalid digit found in string");
+  });
+});
+```
+"##
+)]
+#[cfg_attr(
+    feature = "tracing",
+    doc = r##"
+```edition2018
+use rayon::prelude::*;
+use skip_error::ParallelSkipError;
+let pool = rayon::ThreadPoolBuilder::new()
+  .num_threads(1)
+  .thread_name(|index| format!("importer-{}", index))
+  .build()
+  .unwrap();
+pool.install(|| {
+  testing_logger::setup();
+  let numbers: Vec<u32> = vec!["1", "two", "3"]
+    .into_par_iter()
+    .map(|string_number| string_number.parse())
+    .skip_error_and_log(tracing::Level::WARN)
+    .with_thread_name()
+    .collect();
+  assert_eq!(numbers, vec![1, 3]);
+  testing_logger::validate(|captured_logs| {
+    assert!(captured_logs[0].body.contains("importer-0"));
+  });
+});
+```
+"##
+)]
+pub struct ParSkipError<I> {
+    inner: I,
+    skipped: Arc<AtomicUsize>,
+    skipper: Skipper,
+}
+
+impl<I> ParSkipError<I> {
+    skip_adapter_methods!();
+
+    fn new(inner: I, skipper: Skipper) -> Self {
+        ParSkipError {
+            inner,
+            skipped: Arc::new(AtomicUsize::new(0)),
+            skipper,
+        }
+    }
+
+    /// Returns a shared counter of the skipped errors, updated by all the
+    /// threads during the iteration, for example to display a progress bar.
+    ///
+    /// ```edition2018
+    /// use rayon::prelude::*;
+    /// use skip_error::ParallelSkipError;
+    /// use std::sync::atomic::Ordering;
+    /// let numbers = ["1", "two", "3", "four"]
+    ///   .par_iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error();
+    /// let skipped = numbers.skipped_counter();
+    /// assert_eq!(numbers.count(), 2);
+    /// assert_eq!(skipped.load(Ordering::Relaxed), 2);
+    /// ```
+    pub fn skipped_counter(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.skipped)
+    }
+
+    /// Allows to skip at most `budget` errors: the next error is skipped too,
+    /// and yields a `Err(TooManyErrors)`. Collecting into a `Result` then
+    /// stops the computation on all the threads.
+    ///
+    /// ```edition2018
+    /// use rayon::prelude::*;
+    /// use skip_error::{ParallelSkipError, TooManyErrors};
+    /// let numbers: Result<Vec<u32>, TooManyErrors> = ["1", "two", "3", "four", "five"]
+    ///   .par_iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error()
+    ///   .error_budget(1)
+    ///   .collect();
+    /// assert_eq!(numbers, Err(TooManyErrors::new(1)));
+    ///
+    /// let numbers: Result<Vec<u32>, TooManyErrors> = ["1", "two", "3"]
+    ///   .par_iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error()
+    ///   .error_budget(1)
+    ///   .collect();
+    /// assert_eq!(numbers, Ok(vec![1, 3]));
+    /// ```
+    pub fn error_budget(self, budget: usize) -> ParErrorBudget<I> {
+        ParErrorBudget {
+            inner: self,
+            budget,
+        }
+    }
+
+    #[cfg_attr(
+        not(any(feature = "log", feature = "tracing", feature = "defmt")),
+        allow(unused_mut)
+    )]
+    fn into_parts(mut self) -> (I, ParSkipper) {
+        // Resolved once, so that the threads don't lock the global formatter
+        // for each error
+        #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+        if self.skipper.formatter.is_none() {
+            self.skipper.formatter = crate::format::global_formatter();
+        }
+        let skipper = ParSkipper {
+            skipped: self.skipped,
+            skipper: Arc::new(Mutex::new(self.skipper)),
+        };
+        (self.inner, skipper)
+    }
+}
+
+// Shares the skipper of the adapter between the jobs of the pool.
+#[derive(Clone)]
+struct ParSkipper {
+    skipped: Arc<AtomicUsize>,
+    skipper: Arc<Mutex<Skipper>>,
+}
+
+impl ParSkipper {
+    fn buffer<E>(&self) -> Buffer<E>
+    where
+        E: std::fmt::Display,
+    {
+        Buffer {
+            shared: self.clone(),
+            ok: 0,
+            errors: Vec::new(),
+        }
+    }
+}
+
+// The values and the errors of a job, not yet handled by the shared skipper.
+struct Buffer<E>
+where
+    E: std::fmt::Display,
+{
+    shared: ParSkipper,
+    ok: usize,
+    errors: Vec<E>,
+}
+
+impl<E> Buffer<E>
+where
+    E: std::fmt::Display,
+{
+    // Returns the number of errors skipped so far by all the jobs, including
+    // this one.
+    fn skip(&mut self, error: E) -> usize {
+        let skipped = self.shared.skipped.fetch_add(1, Ordering::Relaxed) + 1;
+        self.errors.push(error);
+        if self.errors.len() >= BUFFER_SIZE {
+            self.flush();
+        }
+        skipped
+    }
+
+    fn flush(&mut self) {
+        if self.ok == 0 && self.errors.is_empty() {
+            return;
+        }
+        let mut skipper = self
+            .shared
+            .skipper
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        for _ in 0..std::mem::take(&mut self.ok) {
+            skipper.ok();
+        }
+        for error in self.errors.drain(..) {
+            skipper.skip(&error);
+        }
+    }
+}
+
+impl<E> Drop for Buffer<E>
+where
+    E: std::fmt::Display,
+{
+    fn drop(&mut self) {
+        // The errors may panic, with `--cfg skip_error_panic_on_skip`
+        if !std::thread::panicking() {
+            self.flush();
+        }
+    }
+}
+
+impl<I, T, E> ParallelIterator for ParSkipError<I>
+where
+    I: ParallelIterator<Item = Result<T, E>>,
+    T: Send,
+    E: std::fmt::Display + Send,
+{
+    type Item = T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<T>,
+    {
+        let (inner, skipper) = self.into_parts();
+        inner
+            .map_init(
+                move || skipper.buffer(),
+                |buffer, result| match result {
+                    Ok(value) => {
+                        buffer.ok += 1;
+                        Some(value)
+                    }
+                    Err(error) => {
+                        buffer.skip(error);
+                        None
+                    }
+                },
+            )
+            .filter_map(|value| value)
+            .drive_unindexed(consumer)
+    }
+}
+
+/// A parallel iterator that ignore errors, within a budget, and yields
+/// `Err(TooManyErrors)` if the budget is exceeded
+///
+/// Collecting it into a `Result` stops the whole computation as soon as
+/// possible once the budget is exceeded.
+///
+/// This `struct` is created by [`ParSkipError::error_budget()`].
+pub struct ParErrorBudget<I> {
+    inner: ParSkipError<I>,
+    budget: usize,
+}
+
+impl<I, T, E> ParallelIterator for ParErrorBudget<I>
+where
+    I: ParallelIterator<Item = Result<T, E>>,
+    T: Send,
+    E: std::fmt::Display + Send,
+{
+    type Item = Result<T, TooManyErrors>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        let budget = self.budget;
+        let (inner, skipper) = self.inner.into_parts();
+        inner
+            .map_init(
+                move || skipper.buffer(),
+                move |buffer, result| match result {
+                    Ok(value) => {
+                        buffer.ok += 1;
+                        Some(Ok(value))
+                    }
+                    Err(error) => {
+                        (buffer.skip(error) > budget).then(|| Err(TooManyErrors::new(budget)))
+                    }
+                },
+            )
+            .filter_map(|value| value)
+            .drive_unindexed(consumer)
+    }
+}
+
+/// Trait to extend any [`ParallelIterator`] where the
+/// [`ParallelIterator::Item`] is a [`Result`]. This allows to skip errors and
+/// keep only the `Ok()` values, on all the threads of the pool.
+pub trait ParallelSkipError<T, E>: ParallelIterator<Item = Result<T, E>> {
+    /// Skip all errors of the [`Result`] in the original [`ParallelIterator`].
+    ///
+    /// ```edition2018
+    /// use rayon::prelude::*;
+    /// use skip_error::ParallelSkipError;
+    /// let mut numbers: Vec<u32> = ["1", "2", "three", "4"]
+    ///   .par_iter()
+    ///   .map(|string_number| string_number.parse())
+    ///   .skip_error()
+    ///   .collect();
+    /// numbers.sort();
+    /// assert_eq!(numbers, vec![1, 2, 4]);
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `skip_error_and_log()`"
+        )
+    )]
+    fn skip_error(self) -> ParSkipError<Self> {
+        ParSkipError::new(self, Skipper::new())
+    }
+
+    /// Skip all errors of the [`Result`] in the original [`ParallelIterator`].
+    /// This also allows to log the errors, choosing which level to use (see
+    /// [`SkipError::skip_error_and_log()`](crate::SkipError::skip_error_and_log)).
+    ///
+    /// ```edition2018
+    /// use rayon::prelude::*;
+    /// use skip_error::ParallelSkipError;
+    /// let numbers: Vec<u32> = ["1", "2", "three", "4"]
+    ///   .par_iter()
+    ///   .map(|string_number| string_number.parse())
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .skip_error_and_log(log::Level::Warn)"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .skip_error_and_log(tracing::Level::WARN)"
+    )]
+    ///   .collect();
+    /// assert_eq!(numbers.len(), 3);
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn skip_error_and_log<L>(self, log_level: L) -> ParSkipError<Self>
+    where
+        L: Into<crate::Level>,
+    {
+        ParSkipError::new(self, Skipper::with_log_level(log_level.into()))
+    }
+
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    level_adapter_methods!(ParSkipError<Self>);
+
+    /// Drives the original [`ParallelIterator`], collecting the `Ok()` values
+    /// into a collection and keeping all the `Err()` values, so the errors can
+    /// still be reported once the iteration is over.
+    ///
+    /// ```edition2018
+    /// use rayon::prelude::*;
+    /// use skip_error::ParallelSkipError;
+    /// let (numbers, errors): (Vec<u32>, _) = ["1", "2", "three", "4"]
+    ///   .par_iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .collect_ok_and_errors();
+    /// assert_eq!(numbers, vec![1, 2, 4]);
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors[0].to_string(), "invalid digit found in string");
+    /// ```
+    fn collect_ok_and_errors<C>(self) -> (C, Vec<E>)
+    where
+        C: Default + Send + ParallelExtend<T>,
+        T: Send,
+        E: Send,
+    {
+        self.partition_map(|result| match result {
+            Ok(value) => Either::Left(value),
+            Err(error) => Either::Right(error),
+        })
+    }
+}
+
+impl<I, T, E> ParallelSkipError<T, E> for I where I: ParallelIterator<Item = Result<T, E>> {}
@@ -0,0 +1,66 @@
+//! Recovering a value for some of the errors, skipping the others.
+use crate::Skipper;
+
+/// An iterator replacing some errors by the value recovered from them, and
+/// skipping the others.
+///
+/// This `struct` is created by [`SkipError::recover_or_skip()`](crate::SkipError::recover_or_skip).
+pub struct RecoverOrSkip<I, F> {
+    inner: I,
+    recover: F,
+    skipper: Skipper,
+    recovered: usize,
+}
+
+impl<I, F> RecoverOrSkip<I, F> {
+    skip_adapter_methods!();
+    budget_adapter_methods!();
+
+    pub(crate) fn new(inner: I, recover: F, skipper: Skipper) -> Self {
+        RecoverOrSkip {
+            inner,
+            recover,
+            skipper,
+            recovered: 0,
+        }
+    }
+
+    /// Number of errors replaced by a recovered value, which are counted
+    /// neither as `Ok()` values nor as skipped errors in the
+    /// [`SkipReport`](crate::SkipReport).
+    pub fn recovered(&self) -> usize {
+        self.recovered
+    }
+}
+
+impl<I, F, T, E> Iterator for RecoverOrSkip<I, F>
+where
+    I: Iterator<Item = Result<T, E>>,
+    F: FnMut(&E) -> Option<T>,
+    E: core::fmt::Display,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.skipper.exhausted() {
+            match self.inner.next()? {
+                Ok(value) => {
+                    self.skipper.ok();
+                    return Some(value);
+                }
+                Err(error) => match (self.recover)(&error) {
+                    Some(value) => {
+                        self.recovered += 1;
+                        return Some(value);
+                    }
+                    None => self.skipper.skip(&error),
+                },
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
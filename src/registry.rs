@@ -0,0 +1,55 @@
+//! Thread-local registry of the skipped errors.
+use std::cell::RefCell;
+
+thread_local! {
+    static SKIPPED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+pub(crate) fn record(error: &dyn std::fmt::Display) {
+    SKIPPED.with(|skipped| skipped.borrow_mut().push(error.to_string()));
+}
+
+/// Takes all the errors skipped on the current thread since the last call,
+/// leaving the registry empty.
+///
+/// Every skip, either with one of the macros (like
+/// [`skip_error!`](crate::skip_error)) or with one of the adapters (like
+/// [`SkipError::skip_error()`](crate::SkipError::skip_error)),
+/// records the error, rendered with [`std::fmt::Display`], in a thread-local
+/// registry. This lets library code be lenient while the application decides
+/// afterwards whether the failures matter.
+///
+/// Note that the registry grows with each skipped error until it is taken.
+///
+/// ```edition2018
+/// # #[macro_use]
+/// # extern crate skip_error;
+/// # fn main() {
+/// for string_number in &["1", "2", "three", "4"] {
+///   let number: u32 = skip_error!(string_number.parse());
+/// }
+/// let skipped = skip_error::take_skipped();
+/// assert_eq!(skipped, vec!["invalid digit found in string"]);
+/// assert!(skip_error::take_skipped().is_empty());
+/// # }
+/// ```
+///
+/// The errors skipped silently with the macros don't need to implement
+/// [`std::fmt::Display`]: the errors which don't are recorded as the name of
+/// their type.
+///
+/// ```edition2018
+/// # #[macro_use]
+/// # extern crate skip_error;
+/// # fn main() {
+/// struct Unreadable;
+/// for result in vec![Ok(1), Err(Unreadable)] {
+///   let number: u32 = skip_error!(result);
+/// }
+/// let skipped = skip_error::take_skipped();
+/// assert!(skipped[0].ends_with("Unreadable"));
+/// # }
+/// ```
+pub fn take_skipped() -> Vec<String> {
+    SKIPPED.with(|skipped| std::mem::take(&mut *skipped.borrow_mut()))
+}
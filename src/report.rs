@@ -0,0 +1,466 @@
+//! Statistics about the skipped errors.
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A summary of an iteration, counting the values which were kept and the
+/// errors which were skipped.
+///
+/// ```edition2018
+/// use skip_error::SkipError;
+/// let mut numbers = ["1", "2", "three", "4"]
+///   .iter()
+///   .map(|string_number| string_number.parse::<u32>())
+///   .skip_error();
+/// let collected: Vec<u32> = numbers.by_ref().collect();
+/// assert_eq!(collected, vec![1, 2, 4]);
+/// assert_eq!(numbers.report().ok(), 3);
+/// assert_eq!(numbers.report().skipped(), 1);
+/// ```
+///
+/// With the feature `serde`, the report implements `serde::Serialize`.
+#[cfg_attr(
+    feature = "serde",
+    doc = r##"
+```edition2018
+use skip_error::SkipError;
+let mut numbers = ["1", "2", "three", "4"]
+  .iter()
+  .map(|string_number| string_number.parse::<u32>())
+  .skip_error();
+numbers.by_ref().for_each(drop);
+let json = serde_json::to_string(&numbers.report()).unwrap();
+assert_eq!(json, r#"{"ok":3,"skipped":1}"#);
+```
+"##
+)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SkipReport {
+    ok: usize,
+    skipped: usize,
+    #[cfg(feature = "timing")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    first_skipped_after: Option<core::time::Duration>,
+    #[cfg(feature = "timing")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    last_skipped_after: Option<core::time::Duration>,
+}
+
+impl SkipReport {
+    /// Number of `Ok()` values which were kept.
+    pub fn ok(&self) -> usize {
+        self.ok
+    }
+
+    /// Number of `Err()` values which were skipped.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Proportion of the values which were skipped errors, between `0.0` and
+    /// `1.0`, or `0.0` if there were no values.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// let mut numbers = ["1", "2", "three", "4"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error();
+    /// numbers.by_ref().for_each(drop);
+    /// assert_eq!(numbers.report().error_rate(), 0.25);
+    /// ```
+    pub fn error_rate(&self) -> f64 {
+        match self.ok + self.skipped {
+            0 => 0.0,
+            total => self.skipped as f64 / total as f64,
+        }
+    }
+
+    /// Whether at least one error was skipped and no value was kept, which is
+    /// usually a configuration error rather than bad data.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// let mut numbers = ["one", "two"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error();
+    /// numbers.by_ref().for_each(drop);
+    /// assert!(numbers.report().all_skipped());
+    /// ```
+    pub fn all_skipped(&self) -> bool {
+        self.ok == 0 && self.skipped > 0
+    }
+
+    /// Time elapsed between the start of the iteration and the first skipped
+    /// error, with `with_timing()`, or `None` if no error was skipped.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipError;
+    /// let mut numbers = ["1", "two", "3", "four"]
+    ///   .iter()
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error()
+    ///   .with_timing();
+    /// numbers.by_ref().for_each(drop);
+    /// let report = numbers.report();
+    /// assert!(report.first_skipped_after().unwrap() <= report.last_skipped_after().unwrap());
+    /// ```
+    #[cfg(feature = "timing")]
+    pub fn first_skipped_after(&self) -> Option<core::time::Duration> {
+        self.first_skipped_after
+    }
+
+    /// Time elapsed between the start of the iteration and the last skipped
+    /// error, with `with_timing()`, or `None` if no error was skipped.
+    #[cfg(feature = "timing")]
+    pub fn last_skipped_after(&self) -> Option<core::time::Duration> {
+        self.last_skipped_after
+    }
+
+    // Returns the time elapsed since the previous skipped error, if any
+    #[cfg(feature = "timing")]
+    pub(crate) fn record_skipped_after(
+        &mut self,
+        elapsed: core::time::Duration,
+    ) -> Option<core::time::Duration> {
+        let previous = self.last_skipped_after.replace(elapsed);
+        self.first_skipped_after.get_or_insert(elapsed);
+        previous.map(|previous| elapsed.saturating_sub(previous))
+    }
+
+    /// Adds the counts of `other` to this report, to combine the reports of
+    /// several threads or chunks into the summary of the whole run. With the
+    /// feature `timing`, the earliest first and the latest last skipped errors
+    /// are kept, each measured from the start of its own iteration.
+    ///
+    /// The reports can also be combined with [`Sum`](core::iter::Sum) and
+    /// [`Extend`].
+    ///
+    /// ```edition2018
+    /// use skip_error::{SkipError, SkipReport};
+    /// let reports: Vec<SkipReport> = vec![vec!["1", "two"], vec!["3", "4", "five"]]
+    ///   .into_iter()
+    ///   .map(|chunk| {
+    ///     std::thread::spawn(move || {
+    ///       let mut numbers = chunk.iter().map(|string| string.parse::<u32>()).skip_error();
+    ///       numbers.by_ref().for_each(drop);
+    ///       numbers.report()
+    ///     })
+    ///   })
+    ///   .map(|thread| thread.join().unwrap())
+    ///   .collect();
+    /// let mut total = SkipReport::default();
+    /// total.merge(reports[0]);
+    /// total.merge(reports[1]);
+    /// assert_eq!((total.ok(), total.skipped()), (3, 2));
+    /// assert_eq!(reports.iter().sum::<SkipReport>(), total);
+    /// ```
+    pub fn merge(&mut self, other: SkipReport) {
+        self.ok += other.ok;
+        self.skipped += other.skipped;
+        #[cfg(feature = "timing")]
+        {
+            self.first_skipped_after = match (self.first_skipped_after, other.first_skipped_after) {
+                (Some(first), Some(other)) => Some(first.min(other)),
+                (first, other) => first.or(other),
+            };
+            self.last_skipped_after = self.last_skipped_after.max(other.last_skipped_after);
+        }
+    }
+
+    pub(crate) fn record_ok(&mut self) {
+        self.ok += 1;
+    }
+
+    pub(crate) fn record_skipped(&mut self) {
+        self.skipped += 1;
+    }
+}
+
+impl core::iter::Sum for SkipReport {
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = SkipReport>,
+    {
+        let mut total = SkipReport::default();
+        total.extend(iter);
+        total
+    }
+}
+
+impl<'a> core::iter::Sum<&'a SkipReport> for SkipReport {
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = &'a SkipReport>,
+    {
+        iter.copied().sum()
+    }
+}
+
+impl Extend<SkipReport> for SkipReport {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = SkipReport>,
+    {
+        for report in iter {
+            self.merge(report);
+        }
+    }
+}
+
+impl<'a> Extend<&'a SkipReport> for SkipReport {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a SkipReport>,
+    {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+/// The error of an adapter which skipped more errors than allowed by its
+/// budget.
+///
+/// ```edition2018
+/// use skip_error::SkipError;
+/// let mut numbers = ["1", "two", "3", "four", "five", "6"]
+///   .iter()
+///   .map(|string_number| string_number.parse::<u32>())
+///   .skip_error()
+///   .error_budget(1);
+/// let collected: Vec<u32> = numbers.by_ref().collect();
+/// assert_eq!(collected, vec![1, 3]);
+/// assert_eq!(numbers.report().skipped(), 2);
+/// ```
+#[cfg_attr(
+    feature = "stream",
+    doc = r##"
+A stream can also yield a final `TooManyErrors` when it stops, with
+`SkipErrorStream::try_error_budget()`.
+```edition2018
+use futures::StreamExt;
+use skip_error::{SkipErrorStreamExt, TooManyErrors};
+# futures::executor::block_on(async {
+let numbers: Vec<Result<u32, TooManyErrors>> =
+  futures::stream::iter(vec!["1", "two", "3", "four", "five", "6"])
+    .map(|string_number| string_number.parse::<u32>())
+    .skip_error()
+    .try_error_budget(1)
+    .collect()
+    .await;
+assert_eq!(numbers, vec![Ok(1), Ok(3), Err(TooManyErrors::new(1))]);
+assert_eq!(numbers[2].unwrap_err().to_string(), "more than 1 error skipped");
+# });
+```
+"##
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooManyErrors {
+    budget: usize,
+}
+
+impl TooManyErrors {
+    /// Creates the error of an adapter with a budget of `budget` errors.
+    pub fn new(budget: usize) -> Self {
+        TooManyErrors { budget }
+    }
+
+    /// Number of errors which were allowed to be skipped.
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+}
+
+/// The error of an adapter which stopped because too many of the last items
+/// were errors, see `abort_if_error_rate_exceeds()`.
+///
+/// ```edition2018
+/// use skip_error::{ErrorRateExceeded, SkipError};
+/// let mut numbers = ["1", "2", "three", "4", "five", "six", "7"]
+///   .iter()
+///   .map(|string_number| string_number.parse::<u32>())
+///   .skip_error()
+///   .abort_if_error_rate_exceeds(0.5, 4);
+/// let collected: Vec<u32> = numbers.by_ref().collect();
+/// assert_eq!(collected, vec![1, 2, 4]);
+/// let error = numbers.error_rate_exceeded().unwrap();
+/// assert_eq!(error, ErrorRateExceeded::new(0.5, 4));
+/// assert_eq!(error.to_string(), "more than 50% of the last 4 items failed");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorRateExceeded {
+    threshold: f64,
+    window: usize,
+}
+
+impl ErrorRateExceeded {
+    /// Creates the error of an adapter stopping when more than `threshold`
+    /// of the last `window` items were errors.
+    pub fn new(threshold: f64, window: usize) -> Self {
+        ErrorRateExceeded { threshold, window }
+    }
+
+    /// Proportion of errors which was allowed over the window.
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Number of the last items over which the error rate was computed.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+}
+
+impl core::fmt::Display for ErrorRateExceeded {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "more than {}% of the last {} items failed",
+            self.threshold * 100.0,
+            self.window
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ErrorRateExceeded {}
+
+impl core::fmt::Display for TooManyErrors {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.budget {
+            1 => write!(f, "more than 1 error skipped"),
+            budget => write!(f, "more than {} errors skipped", budget),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TooManyErrors {}
+
+/// The error of [`SkipErrorIter::require_min_ok()`](crate::SkipErrorIter::require_min_ok),
+/// when fewer values than required were kept.
+///
+/// ```edition2018
+/// use skip_error::SkipError;
+/// let numbers: Result<Vec<u32>, _> = ["1", "two", "three", "four"]
+///   .iter()
+///   .map(|string_number| string_number.parse::<u32>())
+///   .skip_error()
+///   .require_min_ok(2);
+/// let error = numbers.unwrap_err();
+/// assert_eq!((error.ok(), error.skipped()), (1, 3));
+/// assert_eq!(error.to_string(), "only 1 of 4 items succeeded, at least 2 required");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotEnoughSuccesses {
+    required: usize,
+    report: SkipReport,
+}
+
+impl NotEnoughSuccesses {
+    pub(crate) fn new(required: usize, report: SkipReport) -> Self {
+        NotEnoughSuccesses { required, report }
+    }
+
+    /// Number of values which were required.
+    pub fn required(&self) -> usize {
+        self.required
+    }
+
+    /// Number of `Ok()` values which were kept.
+    pub fn ok(&self) -> usize {
+        self.report.ok
+    }
+
+    /// Number of `Err()` values which were skipped.
+    pub fn skipped(&self) -> usize {
+        self.report.skipped
+    }
+
+    /// The [`SkipReport`] of the whole iteration.
+    pub fn report(&self) -> SkipReport {
+        self.report
+    }
+}
+
+impl core::fmt::Display for NotEnoughSuccesses {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "only {} of {} items succeeded, at least {} required",
+            self.report.ok,
+            self.report.ok + self.report.skipped,
+            self.required
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NotEnoughSuccesses {}
+
+/// A handle on the [`SkipReport`] of an adapter, updated while the adapter is
+/// iterated, to follow its progress even after the adapter was moved (into a
+/// `for` loop or a thread, for example).
+///
+/// This `struct` is created by the method `report_handle()` of the adapters,
+/// and can be cloned and sent to other threads.
+///
+/// ```edition2018
+/// use skip_error::SkipError;
+/// let mut numbers = ["1", "2", "three", "4"]
+///   .iter()
+///   .map(|string_number| string_number.parse::<u32>())
+///   .skip_error();
+/// let handle = numbers.report_handle();
+/// for number in numbers {
+///   let report = handle.report();
+///   println!("{:.0}% of rows rejected so far", report.error_rate() * 100.0);
+/// }
+/// assert_eq!(handle.report().ok(), 3);
+/// assert_eq!(handle.report().skipped(), 1);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default)]
+pub struct ReportHandle {
+    counts: std::sync::Arc<SharedCounts>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+struct SharedCounts {
+    ok: AtomicUsize,
+    skipped: AtomicUsize,
+}
+
+#[cfg(feature = "std")]
+impl ReportHandle {
+    pub(crate) fn new(report: SkipReport) -> Self {
+        ReportHandle {
+            counts: std::sync::Arc::new(SharedCounts {
+                ok: AtomicUsize::new(report.ok),
+                skipped: AtomicUsize::new(report.skipped),
+            }),
+        }
+    }
+
+    /// Returns the [`SkipReport`] of the adapter at the time of this call.
+    pub fn report(&self) -> SkipReport {
+        SkipReport {
+            ok: self.counts.ok.load(Ordering::Relaxed),
+            skipped: self.counts.skipped.load(Ordering::Relaxed),
+            // The timing is only known by the adapter
+            #[cfg(feature = "timing")]
+            first_skipped_after: None,
+            #[cfg(feature = "timing")]
+            last_skipped_after: None,
+        }
+    }
+
+    pub(crate) fn record_ok(&self) {
+        self.counts.ok.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_skipped(&self) {
+        self.counts.skipped.fetch_add(1, Ordering::Relaxed);
+    }
+}
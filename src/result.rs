@@ -0,0 +1,267 @@
+//! Skipping the error of a single [`Result`], or the absence of the value of
+//! an [`Option`], outside of any loop.
+
+/// Trait to extend [`Result`] with the skipping of its error, for the call
+/// sites outside of a loop where [`skip_error!`](crate::skip_error) and the
+/// adapters do not fit.
+pub trait ResultExt<T, E>: Sized {
+    /// Converts into an [`Option`] like [`Result::ok()`], logging the error
+    /// at the given level like
+    /// [`skip_error_and_log!`](crate::skip_error_and_log).
+    ///
+    /// ```edition2018
+    /// use skip_error::ResultExt;
+    /// # testing_logger::setup();
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "let number: Option<u32> = \"three\".parse().ok_logged(log::Level::Warn);"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "let number: Option<u32> = \"three\".parse().ok_logged(tracing::Level::WARN);"
+    )]
+    /// assert_eq!(number, None);
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs[0].body, "invalid digit found in string");
+    ///   assert_eq!(captured_logs[0].level, log::Level::Warn);
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn ok_logged<L>(self, log_level: L) -> Option<T>
+    where
+        L: Into<crate::Level>,
+        E: core::fmt::Display;
+
+    /// Converts into an [`Option`] like [`Result::ok()`], giving the error to
+    /// `handler`, to count it, send it into a channel or collect it for
+    /// example.
+    ///
+    /// ```edition2018
+    /// use skip_error::ResultExt;
+    /// let mut errors = Vec::new();
+    /// let numbers: Vec<Option<u32>> = ["1", "three"]
+    ///   .iter()
+    ///   .map(|string_number| {
+    ///     string_number
+    ///       .parse::<u32>()
+    ///       .ok_or_handle(|error| errors.push(error.to_string()))
+    ///   })
+    ///   .collect();
+    /// assert_eq!(numbers, vec![Some(1), None]);
+    /// assert_eq!(errors, vec!["invalid digit found in string"]);
+    /// ```
+    ///
+    /// The error is recorded before it is given to `handler`, like the errors
+    /// skipped by the adapters (by the registry, the metrics or the skip
+    /// scopes, for example).
+    #[cfg_attr(
+        feature = "test-util",
+        doc = r##"
+```edition2018
+use skip_error::ResultExt;
+let (number, skipped) = skip_error::test_util::capture(|| "two".parse::<u32>().ok_or_handle(drop));
+assert_eq!(number, None);
+assert_eq!(skipped.len(), 1);
+```
+"##
+    )]
+    fn ok_or_handle<F>(self, handler: F) -> Option<T>
+    where
+        F: FnOnce(E);
+
+    /// Returns the value or, after logging the error like
+    /// [`ResultExt::ok_logged()`], the default value.
+    ///
+    /// ```edition2018
+    /// use skip_error::ResultExt;
+    /// # testing_logger::setup();
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "let retries: u32 = \"three\".parse().unwrap_or_default_logged(log::Level::Warn);"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "let retries: u32 = \"three\".parse().unwrap_or_default_logged(tracing::Level::WARN);"
+    )]
+    /// assert_eq!(retries, 0);
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs[0].body, "invalid digit found in string");
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn unwrap_or_default_logged<L>(self, log_level: L) -> T
+    where
+        L: Into<crate::Level>,
+        T: Default,
+        E: core::fmt::Display,
+    {
+        self.ok_logged(log_level).unwrap_or_default()
+    }
+    /// Converts into an [`Option`] like [`ResultExt::ok_logged()`], logging
+    /// the error followed by the chain of its causes given by
+    /// [`std::error::Error::source()`], separated by `: `.
+    ///
+    /// ```edition2018
+    /// use skip_error::ResultExt;
+    /// use std::{error::Error, fmt};
+    ///
+    /// #[derive(Debug)]
+    /// struct InvalidConfig(std::num::ParseIntError);
+    /// impl fmt::Display for InvalidConfig {
+    ///   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///     write!(f, "invalid configuration")
+    ///   }
+    /// }
+    /// impl Error for InvalidConfig {
+    ///   fn source(&self) -> Option<&(dyn Error + 'static)> {
+    ///     Some(&self.0)
+    ///   }
+    /// }
+    ///
+    /// # testing_logger::setup();
+    /// let port: Result<u32, InvalidConfig> = "eighty".parse().map_err(InvalidConfig);
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "assert_eq!(port.log_err_chain(log::Level::Error), None);"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "assert_eq!(port.log_err_chain(tracing::Level::ERROR), None);"
+    )]
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs[0].body, "invalid configuration: invalid digit found in string");
+    /// });
+    /// ```
+    #[cfg(all(
+        feature = "std",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ))]
+    fn log_err_chain<L>(self, log_level: L) -> Option<T>
+    where
+        L: Into<crate::Level>,
+        E: std::error::Error;
+}
+
+// Renders an error and its causes on one line
+#[cfg(all(
+    feature = "std",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+struct Chain<'a>(&'a dyn std::error::Error);
+
+#[cfg(all(
+    feature = "std",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+impl core::fmt::Display for Chain<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)?;
+        let mut source = self.0.source();
+        while let Some(cause) = source {
+            write!(f, ": {}", cause)?;
+            source = cause.source();
+        }
+        Ok(())
+    }
+}
+
+impl<T, E> ResultExt<T, E> for Result<T, E> {
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn ok_logged<L>(self, log_level: L) -> Option<T>
+    where
+        L: Into<crate::Level>,
+        E: core::fmt::Display,
+    {
+        match self {
+            Ok(value) => Some(value),
+            Err(error) => {
+                let log_level = log_level.into();
+                crate::__skipped!(error, log_level);
+                let error = crate::__formatted(&error, None);
+                crate::__log!(error, log_level);
+                None
+            }
+        }
+    }
+
+    #[cfg(all(
+        feature = "std",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ))]
+    fn log_err_chain<L>(self, log_level: L) -> Option<T>
+    where
+        L: Into<crate::Level>,
+        E: std::error::Error,
+    {
+        match self {
+            Ok(value) => Some(value),
+            Err(error) => {
+                let log_level = log_level.into();
+                crate::__skipped!(error, log_level);
+                let error = Chain(&error);
+                crate::__log!(error, log_level);
+                None
+            }
+        }
+    }
+
+    fn ok_or_handle<F>(self, handler: F) -> Option<T>
+    where
+        F: FnOnce(E),
+    {
+        match self {
+            Ok(value) => Some(value),
+            Err(error) => {
+                crate::__skipped!(error);
+                handler(error);
+                None
+            }
+        }
+    }
+}
+
+/// Trait to extend [`Option`] with the logging of the absence of its value,
+/// mirroring [`ResultExt`].
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+pub trait OptionExt<T>: Sized {
+    /// Logs `message` at the given level if there is no value, returning the
+    /// [`Option`] unchanged.
+    ///
+    /// ```edition2018
+    /// use skip_error::OptionExt;
+    /// use std::collections::HashMap;
+    /// # testing_logger::setup();
+    /// let config: HashMap<&str, u32> = HashMap::new();
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "let port = config.get(\"port\").or_log_none(log::Level::Info, \"no port, using 8080\");"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "let port = config.get(\"port\").or_log_none(tracing::Level::INFO, \"no port, using 8080\");"
+    )]
+    /// assert_eq!(port.copied().unwrap_or(8080), 8080);
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs[0].body, "no port, using 8080");
+    ///   assert_eq!(captured_logs[0].level, log::Level::Info);
+    /// });
+    /// ```
+    fn or_log_none<L, M>(self, log_level: L, message: M) -> Option<T>
+    where
+        L: Into<crate::Level>,
+        M: core::fmt::Display;
+}
+
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+impl<T> OptionExt<T> for Option<T> {
+    fn or_log_none<L, M>(self, log_level: L, message: M) -> Option<T>
+    where
+        L: Into<crate::Level>,
+        M: core::fmt::Display,
+    {
+        if self.is_none() {
+            crate::__log!(message, log_level.into());
+        }
+        self
+    }
+}
@@ -0,0 +1,300 @@
+//! Retry a fallible function on each item before skipping the error.
+use crate::{skipper::Skipper, SkipErrorIter};
+use std::time::Duration;
+
+/// A strategy deciding how long to wait before retrying a failed attempt.
+///
+/// It is implemented for [`NoBackoff`], [`ConstantBackoff`],
+/// [`ExponentialBackoff`] and any closure `FnMut(u32) -> Duration`.
+pub trait Backoff {
+    /// Returns the delay to wait before the retry number `retry` (starting
+    /// at 1).
+    fn delay(&mut self, retry: u32) -> Duration;
+}
+
+impl<F> Backoff for F
+where
+    F: FnMut(u32) -> Duration,
+{
+    fn delay(&mut self, retry: u32) -> Duration {
+        self(retry)
+    }
+}
+
+/// Retry immediately.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoBackoff;
+
+impl Backoff for NoBackoff {
+    fn delay(&mut self, _retry: u32) -> Duration {
+        Duration::from_secs(0)
+    }
+}
+
+/// Wait the same delay before each retry.
+#[derive(Debug, Clone, Copy)]
+pub struct ConstantBackoff(pub Duration);
+
+impl Backoff for ConstantBackoff {
+    fn delay(&mut self, _retry: u32) -> Duration {
+        self.0
+    }
+}
+
+/// Double the delay before each retry, starting from an initial delay and
+/// without exceeding a maximum delay.
+///
+/// ```edition2018
+/// use skip_error::{Backoff, ExponentialBackoff};
+/// use std::time::Duration;
+/// let mut backoff = ExponentialBackoff::new(Duration::from_millis(10), Duration::from_millis(50));
+/// assert_eq!(backoff.delay(1), Duration::from_millis(10));
+/// assert_eq!(backoff.delay(2), Duration::from_millis(20));
+/// assert_eq!(backoff.delay(3), Duration::from_millis(40));
+/// assert_eq!(backoff.delay(4), Duration::from_millis(50));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ExponentialBackoff {
+    initial: Duration,
+    max: Duration,
+}
+
+impl ExponentialBackoff {
+    /// Creates an exponential backoff starting at `initial` and capped at
+    /// `max`.
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        ExponentialBackoff { initial, max }
+    }
+}
+
+impl Backoff for ExponentialBackoff {
+    fn delay(&mut self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial
+            .checked_mul(factor)
+            .map_or(self.max, |delay| delay.min(self.max))
+    }
+}
+
+#[cfg(feature = "tokio")]
+/// Errors which may disappear when retrying, like a timeout.
+///
+/// It is used by [`IsTransient`] to classify the errors of a [`RetryPolicy`].
+pub trait Transient {
+    /// Whether retrying may succeed.
+    fn is_transient(&self) -> bool;
+}
+
+#[cfg(feature = "tokio")]
+impl Transient for std::io::Error {
+    fn is_transient(&self) -> bool {
+        use std::io::ErrorKind;
+        matches!(
+            self.kind(),
+            ErrorKind::Interrupted
+                | ErrorKind::WouldBlock
+                | ErrorKind::TimedOut
+                | ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+        )
+    }
+}
+
+#[cfg(feature = "tokio")]
+/// Decides which errors are worth retrying.
+///
+/// It is implemented for [`IsTransient`] and any closure `Fn(&E) -> bool`.
+pub trait Classifier<E> {
+    /// Whether `error` should be retried.
+    fn is_transient(&self, error: &E) -> bool;
+}
+
+#[cfg(feature = "tokio")]
+impl<E, F> Classifier<E> for F
+where
+    F: Fn(&E) -> bool,
+{
+    fn is_transient(&self, error: &E) -> bool {
+        self(error)
+    }
+}
+
+#[cfg(feature = "tokio")]
+/// Retry the errors implementing [`Transient`] which are transient.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IsTransient;
+
+#[cfg(feature = "tokio")]
+impl<E> Classifier<E> for IsTransient
+where
+    E: Transient,
+{
+    fn is_transient(&self, error: &E) -> bool {
+        error.is_transient()
+    }
+}
+
+#[cfg(feature = "tokio")]
+/// How to retry the transient errors: how many attempts, how long to wait
+/// between them and which errors are transient.
+///
+/// ```edition2018
+/// use skip_error::{ExponentialBackoff, RetryPolicy};
+/// use std::time::Duration;
+/// let policy = RetryPolicy::new(
+///   3,
+///   ExponentialBackoff::new(Duration::from_millis(10), Duration::from_secs(1)),
+/// )
+/// .with_classifier(|error: &String| error.starts_with("timeout"));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy<B, C> {
+    pub(crate) attempts: u32,
+    pub(crate) backoff: B,
+    pub(crate) classifier: C,
+}
+
+#[cfg(feature = "tokio")]
+impl<B> RetryPolicy<B, IsTransient>
+where
+    B: Backoff,
+{
+    /// Creates a policy trying up to `attempts` times (at least once),
+    /// waiting between the attempts as decided by `backoff`. Only the errors
+    /// implementing [`Transient`] are retried, see
+    /// [`RetryPolicy::with_classifier()`] to change it.
+    pub fn new(attempts: u32, backoff: B) -> Self {
+        RetryPolicy {
+            attempts: attempts.max(1),
+            backoff,
+            classifier: IsTransient,
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<B, C> RetryPolicy<B, C> {
+    /// Use `classifier` to decide which errors are retried.
+    pub fn with_classifier<D>(self, classifier: D) -> RetryPolicy<B, D> {
+        RetryPolicy {
+            attempts: self.attempts,
+            backoff: self.backoff,
+            classifier,
+        }
+    }
+}
+
+/// An iterator retrying a fallible function on each item.
+///
+/// This `struct` is created by [`Retry::map_retry()`].
+pub struct MapRetry<I, B, F> {
+    inner: I,
+    attempts: u32,
+    backoff: B,
+    f: F,
+}
+
+impl<I, B, F, T, E> Iterator for MapRetry<I, B, F>
+where
+    I: Iterator,
+    B: Backoff,
+    F: FnMut(&I::Item) -> Result<T, E>,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.inner.next()?;
+        let mut result = (self.f)(&item);
+        for retry in 1..self.attempts {
+            if result.is_ok() {
+                break;
+            }
+            std::thread::sleep(self.backoff.delay(retry));
+            result = (self.f)(&item);
+        }
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Trait to extend any [`Iterator`] with fallible functions which are retried
+/// before giving up on an item.
+pub trait Retry: Iterator + Sized {
+    /// Applies `f` on each item, trying up to `attempts` times (at least once)
+    /// and waiting between the attempts as decided by `backoff`. The iterator
+    /// yields the first `Ok()` value or the last `Err()` value for each item.
+    ///
+    /// This can be combined with
+    /// [`SkipError::skip_error_and_log()`](crate::SkipError) to log the
+    /// errors remaining after all the attempts.
+    ///
+    /// ```edition2018
+    /// use skip_error::{NoBackoff, Retry};
+    /// let mut calls = 0;
+    /// let results: Vec<Result<u32, String>> = vec![1, 2]
+    ///   .into_iter()
+    ///   .map_retry(3, NoBackoff, |item| {
+    ///     calls += 1;
+    ///     Err(format!("Boom on {}", item))
+    ///   })
+    ///   .collect();
+    /// assert_eq!(calls, 6);
+    /// assert_eq!(results, vec![Err("Boom on 1".to_string()), Err("Boom on 2".to_string())]);
+    /// ```
+    fn map_retry<B, F, T, E>(self, attempts: u32, backoff: B, f: F) -> MapRetry<Self, B, F>
+    where
+        B: Backoff,
+        F: FnMut(&Self::Item) -> Result<T, E>,
+    {
+        MapRetry {
+            inner: self,
+            attempts: attempts.max(1),
+            backoff,
+            f,
+        }
+    }
+
+    /// Applies `f` on each item like [`Retry::map_retry()`], then skips the
+    /// items which are still failing after all the attempts.
+    ///
+    /// ```edition2018
+    /// use skip_error::{ConstantBackoff, Retry};
+    /// use std::time::Duration;
+    /// let mut failures = 0;
+    /// let numbers: Vec<u32> = vec!["1", "2", "three"]
+    ///   .into_iter()
+    ///   .map_retry_then_skip(2, ConstantBackoff(Duration::from_millis(1)), |item| {
+    ///     // "2" fails on the first attempt only, "three" always fails
+    ///     if *item == "2" && failures == 0 {
+    ///       failures += 1;
+    ///       return Err("transient failure".to_string());
+    ///     }
+    ///     item.parse::<u32>().map_err(|error| error.to_string())
+    ///   })
+    ///   .collect();
+    /// assert_eq!(numbers, vec![1, 2]);
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `map_retry(..).skip_error_and_log()`"
+        )
+    )]
+    fn map_retry_then_skip<B, F, T, E>(
+        self,
+        attempts: u32,
+        backoff: B,
+        f: F,
+    ) -> SkipErrorIter<MapRetry<Self, B, F>, T, E>
+    where
+        B: Backoff,
+        F: FnMut(&Self::Item) -> Result<T, E>,
+    {
+        SkipErrorIter::new(self.map_retry(attempts, backoff, f), Skipper::new())
+    }
+}
+
+impl<I> Retry for I where I: Iterator {}
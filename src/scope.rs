@@ -0,0 +1,110 @@
+//! Named scopes counting the errors skipped on the current thread.
+use std::borrow::Cow;
+use std::cell::RefCell;
+
+thread_local! {
+    // The number of errors skipped in each of the open scopes, innermost last
+    static SCOPES: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+#[cfg(all(feature = "log", not(feature = "tracing")))]
+const WARN: crate::Level = log::Level::Warn;
+#[cfg(feature = "tracing")]
+const WARN: crate::Level = tracing::Level::WARN;
+#[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+const WARN: crate::Level = crate::DefmtLevel::Warn;
+
+pub(crate) fn record() {
+    // The thread-local storage may be gone while the thread exits
+    let _ = SCOPES.try_with(|scopes| {
+        for skipped in scopes.borrow_mut().iter_mut() {
+            *skipped += 1;
+        }
+    });
+}
+
+/// A named scope counting the errors skipped on the current thread, which logs
+/// a single summary when it is dropped.
+///
+/// Every skip, either with one of the macros (like [`skip_error!`](crate::skip_error)) or with
+/// one of the adapters (like [`SkipError::skip_error()`](crate::SkipError::skip_error)),
+/// is counted by all the open scopes of the thread, so a scope nested in
+/// another one also counts for the outer scope. Nothing is logged if no error
+/// was skipped in the scope.
+///
+/// It is usually entered with [`skip_scope!`](crate::skip_scope).
+///
+/// ```edition2018
+/// # #[macro_use]
+/// # extern crate skip_error;
+/// # fn main() {
+#[cfg_attr(
+    all(feature = "log", not(feature = "tracing")),
+    doc = "let scope = skip_error::SkipScope::enter(\"import\").with_log_level(log::Level::Info);"
+)]
+#[cfg_attr(
+    feature = "tracing",
+    doc = "let scope = skip_error::SkipScope::enter(\"import\").with_log_level(tracing::Level::INFO);"
+)]
+/// for string_number in &["1", "two", "3", "four"] {
+///   let number: u32 = skip_error!(string_number.parse());
+/// }
+/// assert_eq!(scope.skipped(), 2);
+/// # }
+/// ```
+#[must_use = "the scope ends when it is dropped"]
+pub struct SkipScope {
+    name: Cow<'static, str>,
+    log_level: crate::Level,
+    depth: usize,
+}
+
+impl SkipScope {
+    /// Opens the scope `name`, whose summary is logged at the warning level.
+    pub fn enter(name: impl Into<Cow<'static, str>>) -> Self {
+        let depth = SCOPES.with(|scopes| {
+            let mut scopes = scopes.borrow_mut();
+            scopes.push(0);
+            scopes.len() - 1
+        });
+        SkipScope {
+            name: name.into(),
+            log_level: WARN,
+            depth,
+        }
+    }
+
+    /// Log the summary at `log_level` instead of the warning level.
+    pub fn with_log_level(mut self, log_level: impl Into<crate::Level>) -> Self {
+        self.log_level = log_level.into();
+        self
+    }
+
+    /// The name of the scope.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The number of errors skipped since the scope was entered.
+    pub fn skipped(&self) -> usize {
+        SCOPES
+            .try_with(|scopes| scopes.borrow().get(self.depth).copied())
+            .ok()
+            .flatten()
+            .unwrap_or(0)
+    }
+}
+
+impl Drop for SkipScope {
+    fn drop(&mut self) {
+        let skipped = self.skipped();
+        let _ = SCOPES.try_with(|scopes| scopes.borrow_mut().truncate(self.depth));
+        if skipped > 0 {
+            let message = match skipped {
+                1 => format!("{}: 1 error skipped", self.name),
+                _ => format!("{}: {} errors skipped", self.name, skipped),
+            };
+            crate::__log!(message, self.log_level);
+        }
+    }
+}
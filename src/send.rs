@@ -0,0 +1,165 @@
+//! Support for the write side of asynchronous pipelines, with
+//! [`futures_sink::Sink`].
+use crate::skipper::Skipper;
+use futures_core::ready;
+use futures_sink::Sink;
+use std::{
+    convert::Infallible,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+pin_project_lite::pin_project! {
+    /// A sink that ignore the errors of the sends
+    pub struct SkipErrorSink<Si> {
+        #[pin]
+        inner: Si,
+        skipper: Skipper,
+        drop_next: bool,
+    }
+}
+
+impl<Si> SkipErrorSink<Si> {
+    skip_adapter_methods!();
+
+    /// Consumes this adapter, returning the underlying sink.
+    pub fn into_inner(self) -> Si {
+        self.inner
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<Si> SkipErrorSink<Si> {
+    span_adapter_methods!();
+}
+
+#[cfg(all(
+    feature = "tokio",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+impl<Si> SkipErrorSink<Si> {
+    rate_limit_adapter_methods!();
+}
+
+impl<Si, Item> Sink<Item> for SkipErrorSink<Si>
+where
+    Si: Sink<Item>,
+    Si::Error: std::fmt::Display,
+{
+    type Error = Infallible;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        let this = self.project();
+        if let Err(error) = ready!(this.inner.poll_ready(cx)) {
+            // The item which was about to be sent is dropped in `start_send()`
+            this.skipper.skip(&error);
+            *this.drop_next = true;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Infallible> {
+        let this = self.project();
+        if std::mem::take(this.drop_next) {
+            return Ok(());
+        }
+        match this.inner.start_send(item) {
+            Ok(()) => this.skipper.ok(),
+            Err(error) => this.skipper.skip(&error),
+        }
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        let this = self.project();
+        if let Err(error) = ready!(this.inner.poll_flush(cx)) {
+            this.skipper.skip(&error);
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        let this = self.project();
+        if let Err(error) = ready!(this.inner.poll_close(cx)) {
+            this.skipper.skip(&error);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Trait to extend any [`Sink`], so the failed sends are skipped instead of
+/// failing the whole pipeline. The resulting [`SkipErrorSink`] never fails:
+/// the items which could not be sent are dropped.
+pub trait SkipErrorSinkExt<Item>: Sink<Item> + Sized {
+    /// Skip all errors of the original [`Sink`].
+    ///
+    /// ```edition2018
+    /// use futures::SinkExt;
+    /// use skip_error::SkipErrorSinkExt;
+    /// # futures::executor::block_on(async {
+    /// let (sender, receiver) = futures::channel::mpsc::unbounded::<u32>();
+    /// drop(receiver);
+    /// let mut sink = sender.skip_send_error();
+    /// sink.send(1).await.unwrap();
+    /// sink.send(2).await.unwrap();
+    /// assert_eq!(sink.report().ok(), 0);
+    /// assert_eq!(sink.report().skipped(), 2);
+    /// # });
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `skip_send_error_and_log()`"
+        )
+    )]
+    fn skip_send_error(self) -> SkipErrorSink<Self> {
+        SkipErrorSink {
+            inner: self,
+            skipper: Skipper::new(),
+            drop_next: false,
+        }
+    }
+
+    /// Skip all errors of the original [`Sink`], logging them (see
+    /// [`SkipErrorStreamExt::skip_error_and_log()`](crate::SkipErrorStreamExt::skip_error_and_log)).
+    ///
+    /// ```edition2018
+    /// use futures::{SinkExt, StreamExt};
+    /// use skip_error::SkipErrorSinkExt;
+    /// # testing_logger::setup();
+    /// # futures::executor::block_on(async {
+    /// let (sender, receiver) = futures::channel::mpsc::channel::<u32>(4);
+    /// let mut sink = sender
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .skip_send_error_and_log(log::Level::Warn);"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .skip_send_error_and_log(tracing::Level::WARN);"
+    )]
+    /// sink.send(1).await.unwrap();
+    /// drop(receiver);
+    /// sink.send(2).await.unwrap();
+    /// assert_eq!(sink.report().ok(), 1);
+    /// assert_eq!(sink.report().skipped(), 1);
+    /// # });
+    /// testing_logger::validate(|captured_logs| {
+    ///   let log = captured_logs.iter().find(|log| log.level == log::Level::Warn).unwrap();
+    ///   assert!(log.body.contains("receiver is gone"));
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn skip_send_error_and_log<L>(self, log_level: L) -> SkipErrorSink<Self>
+    where
+        L: Into<crate::Level>,
+    {
+        SkipErrorSink {
+            inner: self,
+            skipper: Skipper::with_log_level(log_level.into()),
+            drop_next: false,
+        }
+    }
+}
+
+impl<Si, Item> SkipErrorSinkExt<Item> for Si where Si: Sink<Item> {}
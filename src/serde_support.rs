@@ -0,0 +1,114 @@
+//! Deserialization of the sequences skipping their invalid elements.
+use crate::Skipper;
+use serde::{
+    de::{DeserializeOwned, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use std::{fmt, marker::PhantomData, ops::Deref};
+
+/// A sequence deserialized element by element, skipping the elements which
+/// fail to deserialize instead of failing the whole document.
+///
+/// The skipped elements are logged at the warning level when the feature
+/// `log` or `tracing` is enabled, and recorded like the other skipped errors.
+/// Each element is first deserialized into a [`serde_json::Value`], so the
+/// format must be self-describing (like JSON, YAML or TOML).
+///
+/// ```edition2018
+/// use serde::Deserialize;
+/// use skip_error::LenientVec;
+/// #[derive(Deserialize)]
+/// struct Row {
+///   id: u32,
+/// }
+/// let rows: LenientVec<Row> =
+///   serde_json::from_str(r#"[{"id": 1}, {"id": "two"}, {}, {"id": 3}]"#).unwrap();
+/// let ids: Vec<u32> = rows.iter().map(|row| row.id).collect();
+/// assert_eq!(ids, vec![1, 3]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LenientVec<T>(pub Vec<T>);
+
+impl<T> Deref for LenientVec<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> From<LenientVec<T>> for Vec<T> {
+    fn from(lenient_vec: LenientVec<T>) -> Self {
+        lenient_vec.0
+    }
+}
+
+impl<'de, T> Deserialize<'de> for LenientVec<T>
+where
+    T: DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(LenientVecVisitor(PhantomData))
+    }
+}
+
+struct LenientVecVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for LenientVecVisitor<T>
+where
+    T: DeserializeOwned,
+{
+    type Value = LenientVec<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        #[cfg(all(feature = "log", not(feature = "tracing")))]
+        let mut skipper = Skipper::with_log_level(log::Level::Warn);
+        #[cfg(feature = "tracing")]
+        let mut skipper = Skipper::with_log_level(tracing::Level::WARN);
+        #[cfg(not(any(feature = "log", feature = "tracing")))]
+        let mut skipper = Skipper::new();
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(value) = seq.next_element::<serde_json::Value>()? {
+            match T::deserialize(value) {
+                Ok(value) => {
+                    skipper.ok();
+                    values.push(value);
+                }
+                Err(error) => skipper.skip(&error),
+            }
+        }
+        Ok(LenientVec(values))
+    }
+}
+
+/// Deserializes a sequence like [`LenientVec`], to be used with
+/// `#[serde(deserialize_with = "skip_error::deserialize_lenient_vec")]`.
+///
+/// ```edition2018
+/// use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct Document {
+///   #[serde(deserialize_with = "skip_error::deserialize_lenient_vec")]
+///   numbers: Vec<u32>,
+/// }
+/// let document: Document =
+///   serde_json::from_str(r#"{"numbers": [1, "two", 3, -4]}"#).unwrap();
+/// assert_eq!(document.numbers, vec![1, 3]);
+/// ```
+pub fn deserialize_lenient_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    LenientVec::deserialize(deserializer).map(Vec::from)
+}
@@ -0,0 +1,349 @@
+//! Sinks receiving the errors skipped by an adapter.
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::{BufWriter, Write};
+
+/// A skipped error, with some information about where it was skipped.
+#[derive(Clone, Copy)]
+pub struct SkippedError<'a> {
+    error: &'a dyn fmt::Display,
+    type_name: &'static str,
+    index: Option<usize>,
+    #[cfg(feature = "alloc")]
+    name: Option<&'a str>,
+    #[cfg(feature = "tracing-error")]
+    span_trace: Option<&'a tracing_error::SpanTrace>,
+}
+
+impl<'a> SkippedError<'a> {
+    pub(crate) fn new<E>(error: &'a E, index: Option<usize>) -> Self
+    where
+        E: fmt::Display,
+    {
+        SkippedError {
+            error,
+            type_name: core::any::type_name::<E>(),
+            index,
+            #[cfg(feature = "alloc")]
+            name: None,
+            #[cfg(feature = "tracing-error")]
+            span_trace: None,
+        }
+    }
+
+    // The same skipped error, displayed as `error`
+    #[cfg(feature = "alloc")]
+    pub(crate) fn with_error(self, error: &'a dyn fmt::Display) -> Self {
+        SkippedError { error, ..self }
+    }
+
+    // The same skipped error, with the type name of the original error
+    #[cfg(feature = "observer")]
+    pub(crate) fn with_type_name(self, type_name: &'static str) -> Self {
+        SkippedError { type_name, ..self }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn with_name(self, name: Option<&'a str>) -> Self {
+        SkippedError { name, ..self }
+    }
+
+    #[cfg(feature = "tracing-error")]
+    pub(crate) fn with_span_trace(self, span_trace: &'a tracing_error::SpanTrace) -> Self {
+        SkippedError {
+            span_trace: Some(span_trace),
+            ..self
+        }
+    }
+
+    /// The skipped error itself.
+    pub fn error(&self) -> &'a dyn fmt::Display {
+        self.error
+    }
+
+    /// The name of the type of the skipped error, as given by
+    /// [`std::any::type_name()`].
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The position of the skipped error in the original iterator, if the
+    /// error was skipped by an adapter (the macros don't know about it).
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// The name of the adapter which skipped the error, if it was given one
+    /// with `named()`.
+    #[cfg(feature = "alloc")]
+    pub fn name(&self) -> Option<&'a str> {
+        self.name
+    }
+
+    /// The spans in which the error was skipped, if it was skipped by an
+    /// adapter.
+    ///
+    /// ```edition2018
+    /// use skip_error::{SkipError, SkipSink, SkippedError};
+    /// use tracing_subscriber::layer::SubscriberExt;
+    ///
+    /// struct SpanTraces(std::sync::mpsc::Sender<String>);
+    /// impl SkipSink for SpanTraces {
+    ///   fn skipped(&mut self, skipped: &SkippedError<'_>) {
+    ///     let _ = self.0.send(skipped.span_trace().unwrap().to_string());
+    ///   }
+    /// }
+    ///
+    /// let subscriber = tracing_subscriber::registry().with(tracing_error::ErrorLayer::default());
+    /// let (sender, receiver) = std::sync::mpsc::channel();
+    /// tracing::subscriber::with_default(subscriber, || {
+    ///   let _span = tracing::info_span!("import").entered();
+    ///   let numbers: Vec<u32> = ["1", "two", "3"]
+    ///     .iter()
+    ///     .map(|string_number| string_number.parse())
+    ///     .skip_error()
+    ///     .with_sink(SpanTraces(sender))
+    ///     .collect();
+    ///   assert_eq!(numbers, vec![1, 3]);
+    /// });
+    /// assert!(receiver.recv().unwrap().contains("import"));
+    /// ```
+    #[cfg(feature = "tracing-error")]
+    pub fn span_trace(&self) -> Option<&'a tracing_error::SpanTrace> {
+        self.span_trace
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// An owned version of a [`SkippedError`], where the error has been rendered
+/// with [`std::fmt::Display`], so it can be kept or sent to another thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedSkippedError {
+    message: String,
+    type_name: &'static str,
+    index: Option<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedSkippedError {
+    pub(crate) fn new(message: String, type_name: &'static str, index: Option<usize>) -> Self {
+        OwnedSkippedError {
+            message,
+            type_name,
+            index,
+        }
+    }
+
+    /// The skipped error, rendered with [`std::fmt::Display`].
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The name of the type of the skipped error.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The position of the skipped error in the original iterator, if any.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<&SkippedError<'_>> for OwnedSkippedError {
+    fn from(skipped: &SkippedError<'_>) -> Self {
+        OwnedSkippedError::new(
+            skipped.error().to_string(),
+            skipped.type_name(),
+            skipped.index(),
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for OwnedSkippedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// A destination for the errors skipped by an adapter, added with
+/// [`SkipErrorIter::with_sink()`](crate::SkipErrorIter::with_sink).
+///
+/// ```edition2018
+/// use skip_error::{SkipError, SkipSink, SkippedError};
+///
+/// struct Indexes(std::sync::mpsc::Sender<usize>);
+/// impl SkipSink for Indexes {
+///   fn skipped(&mut self, skipped: &SkippedError<'_>) {
+///     let _ = self.0.send(skipped.index().unwrap());
+///   }
+/// }
+///
+/// let (sender, receiver) = std::sync::mpsc::channel();
+/// let numbers: Vec<u32> = ["1", "two", "3", "four"]
+///   .iter()
+///   .map(|string_number| string_number.parse())
+///   .skip_error()
+///   .with_sink(Indexes(sender))
+///   .collect();
+/// assert_eq!(numbers, vec![1, 3]);
+/// assert_eq!(receiver.iter().collect::<Vec<_>>(), vec![1, 3]);
+/// ```
+pub trait SkipSink: Send {
+    /// Called for each skipped error.
+    fn skipped(&mut self, skipped: &SkippedError<'_>);
+}
+
+#[cfg(feature = "std")]
+/// A [`SkipSink`] keeping only the last skipped errors, in a buffer of fixed
+/// capacity, so the memory stays bounded however long the iteration is.
+///
+/// The sink is a handle which can be cloned: keep a clone to retrieve the
+/// errors after giving the sink to the adapter.
+///
+/// ```edition2018
+/// use skip_error::{LastErrors, SkipError};
+/// let last_errors = LastErrors::new(2);
+/// let numbers: Vec<u32> = ["one", "2", "three", "four"]
+///   .iter()
+///   .map(|string_number| string_number.parse())
+///   .skip_error()
+///   .with_sink(last_errors.clone())
+///   .collect();
+/// assert_eq!(numbers, vec![2]);
+/// let indexes: Vec<_> = last_errors.errors().iter().map(|error| error.index()).collect();
+/// assert_eq!(indexes, vec![Some(2), Some(3)]);
+/// assert_eq!(last_errors.evicted(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LastErrors {
+    buffer: std::sync::Arc<std::sync::Mutex<RingBuffer>>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug)]
+struct RingBuffer {
+    errors: std::collections::VecDeque<OwnedSkippedError>,
+    capacity: usize,
+    evicted: usize,
+}
+
+#[cfg(feature = "std")]
+impl LastErrors {
+    /// Creates a sink keeping the last `capacity` skipped errors.
+    pub fn new(capacity: usize) -> Self {
+        LastErrors {
+            buffer: std::sync::Arc::new(std::sync::Mutex::new(RingBuffer {
+                errors: std::collections::VecDeque::with_capacity(capacity),
+                capacity,
+                evicted: 0,
+            })),
+        }
+    }
+
+    fn buffer(&self) -> std::sync::MutexGuard<'_, RingBuffer> {
+        self.buffer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Returns the last skipped errors, the oldest first.
+    pub fn errors(&self) -> Vec<OwnedSkippedError> {
+        self.buffer().errors.iter().cloned().collect()
+    }
+
+    /// Number of skipped errors which were evicted to make room for the
+    /// next ones.
+    pub fn evicted(&self) -> usize {
+        self.buffer().evicted
+    }
+}
+
+#[cfg(feature = "std")]
+impl SkipSink for LastErrors {
+    fn skipped(&mut self, skipped: &SkippedError<'_>) {
+        let mut buffer = self.buffer();
+        if buffer.capacity == 0 {
+            buffer.evicted += 1;
+            return;
+        }
+        if buffer.errors.len() == buffer.capacity {
+            buffer.errors.pop_front();
+            buffer.evicted += 1;
+        }
+        buffer.errors.push_back(OwnedSkippedError::from(skipped));
+    }
+}
+
+#[cfg(feature = "std")]
+/// A [`SkipSink`] writing one human-readable line per skipped error to a
+/// writer, typically a rejects file independent of the logs of the process.
+///
+/// Each line is `item <index>: <error>`, or only the error if the index is
+/// unknown. The writes are buffered and flushed when the sink is dropped,
+/// that is when the adapter is dropped. Errors while writing are ignored.
+///
+/// ```edition2018
+/// use skip_error::{SkipError, TextSink};
+/// let path = std::env::temp_dir().join("skip_error_rejects_example.log");
+/// let rejects = std::fs::File::create(&path).unwrap();
+/// let numbers: Vec<u32> = ["1", "two", "3", "four"]
+///   .iter()
+///   .map(|string_number| string_number.parse())
+///   .skip_error()
+///   .with_sink(TextSink::new(rejects))
+///   .collect();
+/// assert_eq!(numbers, vec![1, 3]);
+/// assert_eq!(
+///   std::fs::read_to_string(&path).unwrap(),
+///   "item 1: invalid digit found in string\nitem 3: invalid digit found in string\n"
+/// );
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct TextSink<W>
+where
+    W: Write,
+{
+    writer: BufWriter<W>,
+}
+
+#[cfg(feature = "std")]
+impl<W> TextSink<W>
+where
+    W: Write,
+{
+    /// Creates a sink writing into `writer`.
+    pub fn new(writer: W) -> Self {
+        TextSink {
+            writer: BufWriter::new(writer),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W> SkipSink for TextSink<W>
+where
+    W: Write + Send,
+{
+    fn skipped(&mut self, skipped: &SkippedError<'_>) {
+        let _ = match skipped.index() {
+            Some(index) => writeln!(self.writer, "item {}: {}", index, skipped.error()),
+            None => writeln!(self.writer, "{}", skipped.error()),
+        };
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W> Drop for TextSink<W>
+where
+    W: Write,
+{
+    fn drop(&mut self) {
+        let _ = self.writer.flush();
+    }
+}
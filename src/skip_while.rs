@@ -0,0 +1,59 @@
+//! Skipping the errors only at the beginning of an iteration.
+use crate::Skipper;
+
+/// An iterator skipping the errors while a predicate over the
+/// [`SkipReport`](crate::SkipReport) so far holds, then yielding them.
+///
+/// This `struct` is created by [`SkipError::skip_error_while()`](crate::SkipError::skip_error_while).
+pub struct SkipErrorWhile<I, P> {
+    inner: I,
+    predicate: P,
+    skipper: Skipper,
+    lenient: bool,
+}
+
+impl<I, P> SkipErrorWhile<I, P> {
+    skip_adapter_methods!();
+
+    pub(crate) fn new(inner: I, predicate: P) -> Self {
+        SkipErrorWhile {
+            inner,
+            predicate,
+            skipper: Skipper::new(),
+            lenient: true,
+        }
+    }
+
+    /// Whether the errors are still skipped.
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+}
+
+impl<I, P, T, E> Iterator for SkipErrorWhile<I, P>
+where
+    I: Iterator<Item = Result<T, E>>,
+    P: FnMut(&crate::SkipReport) -> bool,
+    E: core::fmt::Display,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let result = self.inner.next()?;
+            self.lenient = self.lenient && (self.predicate)(&self.skipper.report);
+            match result {
+                Ok(value) => {
+                    self.skipper.ok();
+                    return Some(Ok(value));
+                }
+                Err(error) if self.lenient => self.skipper.skip(&error),
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
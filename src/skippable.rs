@@ -0,0 +1,103 @@
+//! Result-like types whose errors can be skipped.
+
+/// A value which is either an output or an error, like a [`Result`].
+///
+/// The macros, like [`skip_error!`](crate::skip_error), accept any
+/// `Skippable` after `skippable:`, and the iterators of `Skippable` can be turned into iterators
+/// of [`Result`] with [`SkippableIterExt::into_results()`] to use the
+/// adapters. This lets the custom outcome types participate in skipping.
+///
+/// ```edition2018
+/// # #[macro_use]
+/// # extern crate skip_error;
+/// use skip_error::{SkipError, Skippable, SkippableIterExt};
+///
+/// enum Validated {
+///   Valid(u32),
+///   Invalid(&'static str),
+/// }
+///
+/// impl Skippable for Validated {
+///   type Output = u32;
+///   type Error = &'static str;
+///   fn into_result(self) -> Result<u32, &'static str> {
+///     match self {
+///       Validated::Valid(value) => Ok(value),
+///       Validated::Invalid(reason) => Err(reason),
+///     }
+///   }
+/// }
+///
+/// # fn main() {
+/// let samples = vec![Validated::Valid(1), Validated::Invalid("too high"), Validated::Valid(3)];
+/// let numbers: Vec<u32> = samples.into_iter().into_results().skip_error().collect();
+/// assert_eq!(numbers, vec![1, 3]);
+///
+/// for sample in vec![Validated::Invalid("too low"), Validated::Valid(4)] {
+///   let number: u32 = skip_error!(skippable: sample);
+///   assert_eq!(number, 4);
+/// }
+/// # }
+/// ```
+pub trait Skippable {
+    /// Type of the output, kept when skipping.
+    type Output;
+    /// Type of the error, which is skipped.
+    type Error;
+
+    /// Turns the value into a [`Result`].
+    fn into_result(self) -> Result<Self::Output, Self::Error>;
+}
+
+impl<T, E> Skippable for Result<T, E> {
+    type Output = T;
+    type Error = E;
+
+    #[inline]
+    fn into_result(self) -> Result<T, E> {
+        self
+    }
+}
+
+/// An iterator turning each [`Skippable`] into a [`Result`].
+///
+/// This `struct` is created by [`SkippableIterExt::into_results()`].
+pub struct IntoResults<I> {
+    inner: I,
+}
+
+impl<I> Iterator for IntoResults<I>
+where
+    I: Iterator,
+    I::Item: Skippable,
+{
+    type Item = Result<<I::Item as Skippable>::Output, <I::Item as Skippable>::Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(Skippable::into_result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Trait to extend any [`Iterator`] of [`Skippable`] values.
+pub trait SkippableIterExt: Iterator + Sized
+where
+    Self::Item: Skippable,
+{
+    /// Turns each item into a [`Result`], so the errors can be skipped with
+    /// [`SkipError`](crate::SkipError).
+    fn into_results(self) -> IntoResults<Self> {
+        IntoResults { inner: self }
+    }
+}
+
+impl<I> SkippableIterExt for I
+where
+    I: Iterator,
+    I::Item: Skippable,
+{
+}
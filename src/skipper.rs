@@ -0,0 +1,1316 @@
+//! Handling of the skipped errors, shared by all the adapters.
+#[cfg(all(
+    feature = "std",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+use crate::ErrorFormatter;
+#[cfg(feature = "alloc")]
+use crate::SkipSink;
+use crate::{SkipReport, SkippedError};
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+
+#[cfg(feature = "alloc")]
+pub(crate) type Redactor =
+    Box<dyn Fn(&dyn core::fmt::Display) -> alloc::string::String + Send + Sync>;
+
+// Records the error skipped by an adapter, like `__skipped!` but keeping the
+// type of the error when it was redacted
+#[cfg(any(
+    feature = "cadence",
+    feature = "metrics",
+    feature = "observer",
+    feature = "opentelemetry",
+    feature = "prometheus",
+    feature = "registry",
+    feature = "test-util",
+    skip_error_panic_on_skip
+))]
+macro_rules! skipped_as {
+    ($skipped:expr) => {{
+        crate::__scoped();
+        crate::record_as($skipped.error(), $skipped.type_name(), module_path!());
+    }};
+}
+
+#[cfg(not(any(
+    feature = "cadence",
+    feature = "metrics",
+    feature = "observer",
+    feature = "opentelemetry",
+    feature = "prometheus",
+    feature = "registry",
+    feature = "test-util",
+    skip_error_panic_on_skip
+)))]
+macro_rules! skipped_as {
+    ($skipped:expr) => {{
+        crate::__scoped();
+    }};
+}
+
+pub(crate) struct Skipper {
+    pub(crate) report: SkipReport,
+    pub(crate) budget: Option<usize>,
+    // The total cost of the skipped errors, counted against the budget
+    pub(crate) spent: usize,
+    #[cfg(feature = "alloc")]
+    pub(crate) rate_window: Option<RateWindow>,
+    #[cfg(feature = "std")]
+    pub(crate) handle: Option<crate::ReportHandle>,
+    #[cfg(feature = "alloc")]
+    pub(crate) sinks: Vec<Box<dyn SkipSink>>,
+    #[cfg(feature = "alloc")]
+    pub(crate) categories: BTreeMap<&'static str, usize>,
+    #[cfg(feature = "alloc")]
+    pub(crate) redactor: Option<Redactor>,
+    #[cfg(feature = "alloc")]
+    pub(crate) name: Option<Cow<'static, str>>,
+    #[cfg(feature = "alloc")]
+    pub(crate) max_chars: Option<usize>,
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    pub(crate) log_level: Option<crate::Level>,
+    #[cfg(all(
+        feature = "std",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ))]
+    pub(crate) formatter: Option<Box<dyn ErrorFormatter>>,
+    #[cfg(all(
+        feature = "std",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ))]
+    pub(crate) background: Option<crate::background::BackgroundLog>,
+    #[cfg(all(
+        feature = "std",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ))]
+    pub(crate) thread_name: bool,
+    #[cfg(all(
+        feature = "alloc",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ))]
+    pub(crate) context: Option<Cow<'static, str>>,
+    #[cfg(all(
+        feature = "alloc",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ))]
+    pub(crate) repeats: Option<Repeats>,
+    #[cfg(all(
+        feature = "alloc",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ))]
+    pub(crate) batch: Option<Batch>,
+    // The level of the message logged on drop if every item was skipped
+    #[cfg(all(
+        feature = "alloc",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ))]
+    pub(crate) all_skipped_level: Option<crate::Level>,
+    // When the iteration started, with `with_timing()`
+    #[cfg(feature = "timing")]
+    pub(crate) started: Option<std::time::Instant>,
+    #[cfg(feature = "tracing")]
+    pub(crate) span: Option<tracing::Span>,
+    #[cfg(all(
+        feature = "tokio",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ))]
+    pub(crate) rate_limiter: Option<crate::tokio_support::RateLimiter>,
+}
+
+impl Skipper {
+    pub(crate) fn new() -> Self {
+        Skipper {
+            report: SkipReport::default(),
+            budget: None,
+            spent: 0,
+            #[cfg(feature = "alloc")]
+            rate_window: None,
+            #[cfg(feature = "std")]
+            handle: None,
+            #[cfg(feature = "alloc")]
+            sinks: Vec::new(),
+            #[cfg(feature = "alloc")]
+            categories: BTreeMap::new(),
+            #[cfg(feature = "alloc")]
+            redactor: None,
+            #[cfg(feature = "alloc")]
+            name: None,
+            #[cfg(feature = "alloc")]
+            max_chars: None,
+            #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+            log_level: None,
+            #[cfg(all(
+                feature = "std",
+                any(feature = "log", feature = "tracing", feature = "defmt")
+            ))]
+            formatter: None,
+            #[cfg(all(
+                feature = "std",
+                any(feature = "log", feature = "tracing", feature = "defmt")
+            ))]
+            background: None,
+            #[cfg(all(
+                feature = "std",
+                any(feature = "log", feature = "tracing", feature = "defmt")
+            ))]
+            thread_name: false,
+            #[cfg(all(
+                feature = "alloc",
+                any(feature = "log", feature = "tracing", feature = "defmt")
+            ))]
+            context: None,
+            #[cfg(all(
+                feature = "alloc",
+                any(feature = "log", feature = "tracing", feature = "defmt")
+            ))]
+            repeats: None,
+            #[cfg(all(
+                feature = "alloc",
+                any(feature = "log", feature = "tracing", feature = "defmt")
+            ))]
+            batch: None,
+            #[cfg(all(
+                feature = "alloc",
+                any(feature = "log", feature = "tracing", feature = "defmt")
+            ))]
+            all_skipped_level: None,
+            #[cfg(feature = "timing")]
+            started: None,
+            #[cfg(feature = "tracing")]
+            span: None,
+            #[cfg(all(
+                feature = "tokio",
+                any(feature = "log", feature = "tracing", feature = "defmt")
+            ))]
+            rate_limiter: None,
+        }
+    }
+
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    pub(crate) fn with_log_level(log_level: crate::Level) -> Self {
+        let mut skipper = Skipper::new();
+        skipper.log_level = Some(log_level);
+        skipper
+    }
+
+    // Whether more errors were skipped than allowed by the budget, in which
+    // case the adapter stops.
+    #[inline]
+    pub(crate) fn exhausted(&self) -> bool {
+        #[cfg(feature = "alloc")]
+        if self.error_rate_exceeded().is_some() {
+            return true;
+        }
+        self.budget_exceeded()
+    }
+
+    #[inline]
+    pub(crate) fn budget_exceeded(&self) -> bool {
+        self.budget.is_some_and(|budget| self.spent > budget)
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn error_rate_exceeded(&self) -> Option<crate::ErrorRateExceeded> {
+        self.rate_window.as_ref().and_then(RateWindow::exceeded)
+    }
+
+    #[inline]
+    pub(crate) fn ok(&mut self) {
+        self.report.record_ok();
+        #[cfg(feature = "alloc")]
+        if let Some(rate_window) = &mut self.rate_window {
+            rate_window.record(false);
+        }
+        #[cfg(feature = "std")]
+        if let Some(handle) = &self.handle {
+            handle.record_ok();
+        }
+    }
+
+    // Logs the error which stopped the adapter, without skipping it. Only
+    // used by the adapters which need an allocator
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    #[cfg_attr(not(skip_error_no_cold), cold)]
+    pub(crate) fn stop<E>(&mut self, _error: &E)
+    where
+        E: core::fmt::Display,
+    {
+        // Rendered like the skipped errors, so it is redacted and truncated
+        // too
+        #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+        if let Some(log_level) = self.log_level {
+            let _skipped = SkippedError::new(_error, None);
+            #[cfg(feature = "alloc")]
+            let rendered = self.render(_error);
+            #[cfg(feature = "alloc")]
+            let _skipped = match &rendered {
+                Some(rendered) => _skipped.with_error(rendered),
+                None => _skipped,
+            };
+            #[cfg(feature = "std")]
+            let error = crate::format::Formatted::new(_skipped, self.formatter.as_deref());
+            #[cfg(not(feature = "std"))]
+            let error = _skipped.error();
+            let message = Stopped(error);
+            crate::__log!(message, log_level);
+        }
+    }
+
+    // Renders the error with the redactor and the truncation, if any
+    #[cfg(feature = "alloc")]
+    fn render<E>(&self, error: &E) -> Option<alloc::string::String>
+    where
+        E: core::fmt::Display,
+    {
+        let rendered = self.redactor.as_ref().map(|redactor| redactor(error));
+        match self.max_chars {
+            Some(max_chars) => {
+                let message = rendered.unwrap_or_else(|| alloc::string::ToString::to_string(error));
+                Some(truncate(message, max_chars))
+            }
+            None => rendered,
+        }
+    }
+
+    // Only used by the adapters which do not compute any details, all of
+    // them needing `std` except `lender`
+    #[cfg_attr(not(any(feature = "std", feature = "lender")), allow(dead_code))]
+    #[cfg_attr(not(skip_error_no_cold), cold)]
+    pub(crate) fn skip<E>(&mut self, error: &E)
+    where
+        E: core::fmt::Display,
+    {
+        self.skip_with(error, SkipDetails::default());
+    }
+
+    // Like `skip()`, with the details computed by the adapter from the error
+    #[cfg_attr(not(skip_error_no_cold), cold)]
+    pub(crate) fn skip_with<E>(&mut self, error: &E, _details: SkipDetails)
+    where
+        E: core::fmt::Display,
+    {
+        let index = self.report.ok() + self.report.skipped();
+        self.report.record_skipped();
+        #[cfg(feature = "timing")]
+        let _timing = self.started.map(|started| {
+            let elapsed = started.elapsed();
+            (elapsed, self.report.record_skipped_after(elapsed))
+        });
+        self.spent = self.spent.saturating_add(_details.cost.unwrap_or(1));
+        #[cfg(feature = "alloc")]
+        if let Some(rate_window) = &mut self.rate_window {
+            rate_window.record(true);
+        }
+        #[cfg(feature = "std")]
+        if let Some(handle) = &self.handle {
+            handle.record_skipped();
+        }
+        #[cfg(feature = "alloc")]
+        if let Some(category) = _details.category {
+            *self.categories.entry(category).or_insert(0) += 1;
+        }
+        let _skipped = SkippedError::new(error, Some(index));
+        #[cfg(feature = "alloc")]
+        let rendered = self.render(error);
+        #[cfg(feature = "alloc")]
+        let _skipped = match &rendered {
+            Some(rendered) => _skipped.with_error(rendered),
+            None => _skipped,
+        };
+        #[cfg(feature = "alloc")]
+        let _skipped = _skipped.with_name(self.name.as_deref());
+        skipped_as!(_skipped);
+        #[cfg(feature = "observer")]
+        {
+            let event = crate::observer::SkipEvent::new(_skipped, None);
+            #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+            let event = event.with_level(self.log_level);
+            crate::observer::notify(&event);
+        }
+        #[cfg(feature = "tracing-error")]
+        let span_trace = tracing_error::SpanTrace::capture();
+        #[cfg(feature = "tracing-error")]
+        let _skipped = _skipped.with_span_trace(&span_trace);
+        #[cfg(feature = "alloc")]
+        for sink in &mut self.sinks {
+            sink.skipped(&_skipped);
+        }
+        #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+        if let Some(log_level) = self.log_level {
+            #[cfg(feature = "tracing")]
+            let _entered = self.span.as_ref().map(tracing::Span::enter);
+            #[cfg(feature = "std")]
+            let thread = self.thread_name.then(current_thread_name);
+            #[cfg(feature = "std")]
+            let error = crate::format::Formatted::new(_skipped, self.formatter.as_deref());
+            #[cfg(not(feature = "std"))]
+            let error = _skipped.error();
+            #[cfg(feature = "alloc")]
+            let error = WithContext {
+                #[cfg(not(feature = "tracing"))]
+                name: self.name.as_deref(),
+                #[cfg(all(feature = "std", not(feature = "tracing")))]
+                thread: thread.as_deref(),
+                context: self.context.as_deref(),
+                category: _details.category,
+                #[cfg(all(feature = "timing", not(feature = "tracing")))]
+                timing: _timing,
+                error,
+            };
+            #[cfg(feature = "alloc")]
+            if let Some(repeats) = &mut self.repeats {
+                let message = alloc::string::ToString::to_string(&error);
+                if repeats.repeated(&message) {
+                    return;
+                }
+                repeats.flush();
+                repeats.last = Some((message, log_level));
+            }
+            // The repeats are collapsed before the rate limit, so they are
+            // counted even while the logs are suppressed, and the rate limit
+            // only applies to the messages which would be logged
+            #[cfg(feature = "tokio")]
+            let error = match self.rate_limiter.as_mut().map(|limiter| limiter.allow()) {
+                Some(None) => {
+                    // The suppressed message is not logged, so the next ones
+                    // are not its repeats
+                    if let Some(repeats) = &mut self.repeats {
+                        repeats.last = None;
+                    }
+                    return;
+                }
+                Some(Some(suppressed)) => Suppressed { error, suppressed },
+                None => Suppressed {
+                    error,
+                    suppressed: 0,
+                },
+            };
+            #[cfg(feature = "alloc")]
+            if let Some(batch) = &mut self.batch {
+                if let Some(message) = batch.push(alloc::string::ToString::to_string(&error)) {
+                    #[cfg(feature = "std")]
+                    if let Some(background) = &mut self.background {
+                        background.send(message, log_level);
+                        return;
+                    }
+                    crate::__log!(message, log_level);
+                }
+                return;
+            }
+            #[cfg(feature = "std")]
+            if let Some(background) = &mut self.background {
+                background.send(error.to_string(), log_level);
+                return;
+            }
+            #[cfg(feature = "tracing")]
+            {
+                let adapter = self.name.as_deref();
+                let fields = _details.fields.as_ref().map(tracing::field::display);
+                let thread = thread.as_deref();
+                #[cfg(feature = "timing")]
+                let (elapsed, since_previous) = match _timing {
+                    Some((elapsed, since_previous)) => (Some(elapsed), since_previous),
+                    None => (None, None),
+                };
+                #[cfg(not(feature = "timing"))]
+                let (elapsed, since_previous): (
+                    Option<core::time::Duration>,
+                    Option<core::time::Duration>,
+                ) = (None, None);
+                let elapsed = elapsed.map(tracing::field::debug);
+                let since_previous = since_previous.map(tracing::field::debug);
+                crate::__log!(
+                    error,
+                    log_level;
+                    adapter = adapter,
+                    thread = thread,
+                    elapsed = elapsed,
+                    since_previous = since_previous,
+                    fields = fields
+                );
+            }
+            #[cfg(not(feature = "tracing"))]
+            crate::__log!(error, log_level);
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "alloc",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+#[derive(Default)]
+pub(crate) struct Repeats {
+    // The last logged message, with its level
+    last: Option<(alloc::string::String, crate::Level)>,
+    // How many times the last message was not logged again
+    count: usize,
+}
+
+#[cfg(all(
+    feature = "alloc",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+impl Repeats {
+    fn repeated(&mut self, message: &str) -> bool {
+        let repeated = matches!(&self.last, Some((last, _)) if last == message);
+        self.count += usize::from(repeated);
+        repeated
+    }
+
+    fn flush(&mut self) {
+        match &self.last {
+            Some((_, log_level)) if self.count > 0 => {
+                let message = alloc::format!("previous message repeated {} times", self.count);
+                crate::__log!(message, *log_level);
+            }
+            _ => {}
+        }
+        self.count = 0;
+    }
+}
+
+// The skipped errors logged together in a single multi-line message
+#[cfg(all(
+    feature = "alloc",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+pub(crate) struct Batch {
+    messages: Vec<alloc::string::String>,
+    size: usize,
+}
+
+#[cfg(all(
+    feature = "alloc",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+impl Batch {
+    pub(crate) fn new(size: usize) -> Self {
+        Batch {
+            messages: Vec::new(),
+            size: size.max(1),
+        }
+    }
+
+    // Returns the message of the whole batch once it is full
+    fn push(&mut self, message: alloc::string::String) -> Option<alloc::string::String> {
+        self.messages.push(message);
+        match self.messages.len() >= self.size {
+            true => self.flush(),
+            false => None,
+        }
+    }
+
+    fn flush(&mut self) -> Option<alloc::string::String> {
+        if self.messages.is_empty() {
+            return None;
+        }
+        let mut message = match self.messages.len() {
+            1 => alloc::string::String::from("1 error skipped:"),
+            len => alloc::format!("{} errors skipped:", len),
+        };
+        for skipped in self.messages.drain(..) {
+            message.push_str("\n  ");
+            message.push_str(&skipped);
+        }
+        Some(message)
+    }
+}
+
+#[cfg(all(
+    feature = "alloc",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+impl Skipper {
+    // Logs a message which is already rendered, like the last batch
+    fn emit(&mut self, message: alloc::string::String, log_level: crate::Level) {
+        #[cfg(feature = "std")]
+        if let Some(background) = &mut self.background {
+            background.send(message, log_level);
+            return;
+        }
+        crate::__log!(message, log_level);
+    }
+}
+
+// The repeats of the last message, the last batch, and whether every item was
+// skipped, are logged when the adapter is dropped
+#[cfg(all(
+    feature = "alloc",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+impl Drop for Skipper {
+    fn drop(&mut self) {
+        if let Some(repeats) = &mut self.repeats {
+            repeats.flush();
+        }
+        let message = self.batch.as_mut().and_then(Batch::flush);
+        if let (Some(message), Some(log_level)) = (message, self.log_level) {
+            self.emit(message, log_level);
+        }
+        if let Some(log_level) = self.all_skipped_level {
+            if self.report.all_skipped() {
+                let message = match self.report.skipped() {
+                    1 => alloc::string::String::from("the only item failed"),
+                    skipped => alloc::format!("all {} items failed", skipped),
+                };
+                let message = match &self.name {
+                    Some(name) => alloc::format!("[{}] {}", name, message),
+                    None => message,
+                };
+                self.emit(message, log_level);
+            }
+        }
+    }
+}
+
+// The name of the current thread, or its id if it has no name, like the
+// threads of a default `rayon` pool
+#[cfg(all(
+    feature = "std",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+fn current_thread_name() -> String {
+    let thread = std::thread::current();
+    match thread.name() {
+        Some(name) => name.to_string(),
+        None => format!("{:?}", thread.id()),
+    }
+}
+
+// Keeps the first `max_chars` characters of `message`, followed by an
+// ellipsis and the length of the whole message
+#[cfg(feature = "alloc")]
+fn truncate(message: alloc::string::String, max_chars: usize) -> alloc::string::String {
+    match message.char_indices().nth(max_chars) {
+        Some((end, _)) => alloc::format!(
+            "{}... ({} characters)",
+            &message[..end],
+            message.chars().count()
+        ),
+        None => message,
+    }
+}
+
+// The outcomes of the last items, to stop an adapter when too many of them
+// were errors
+#[cfg(feature = "alloc")]
+pub(crate) struct RateWindow {
+    threshold: f64,
+    window: usize,
+    // Whether each of the last items was an error, the oldest first
+    outcomes: alloc::collections::VecDeque<bool>,
+    errors: usize,
+    exceeded: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl RateWindow {
+    pub(crate) fn new(threshold: f64, window: usize) -> Self {
+        let window = window.max(1);
+        RateWindow {
+            threshold,
+            window,
+            outcomes: alloc::collections::VecDeque::with_capacity(window),
+            errors: 0,
+            exceeded: false,
+        }
+    }
+
+    fn record(&mut self, error: bool) {
+        if self.outcomes.len() == self.window && self.outcomes.pop_front() == Some(true) {
+            self.errors -= 1;
+        }
+        self.outcomes.push_back(error);
+        self.errors += usize::from(error);
+        if self.outcomes.len() == self.window
+            && self.errors as f64 > self.threshold * self.window as f64
+        {
+            self.exceeded = true;
+        }
+    }
+
+    fn exceeded(&self) -> Option<crate::ErrorRateExceeded> {
+        self.exceeded
+            .then(|| crate::ErrorRateExceeded::new(self.threshold, self.window))
+    }
+}
+
+// What an adapter knows about a skipped error beyond its rendering
+#[derive(Default)]
+pub(crate) struct SkipDetails {
+    #[cfg(feature = "tracing")]
+    pub(crate) fields: Option<crate::Fields>,
+    #[cfg(feature = "alloc")]
+    pub(crate) category: Option<&'static str>,
+    // The cost of the error for the budget, 1 if unknown
+    pub(crate) cost: Option<usize>,
+}
+
+// An error logged after the context of its adapter and its category, if any
+#[cfg(all(
+    feature = "alloc",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+struct WithContext<'a, D> {
+    // With `tracing`, the name is a field of the event instead
+    #[cfg(not(feature = "tracing"))]
+    name: Option<&'a str>,
+    #[cfg(all(feature = "std", not(feature = "tracing")))]
+    thread: Option<&'a str>,
+    context: Option<&'a str>,
+    category: Option<&'static str>,
+    // The time since the start of the iteration, and since the previous
+    // skipped error
+    #[cfg(all(feature = "timing", not(feature = "tracing")))]
+    timing: Option<(core::time::Duration, Option<core::time::Duration>)>,
+    error: D,
+}
+
+#[cfg(all(
+    feature = "alloc",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+impl<D> core::fmt::Display for WithContext<'_, D>
+where
+    D: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[cfg(not(feature = "tracing"))]
+        if let Some(name) = self.name {
+            write!(f, "[{}] ", name)?;
+        }
+        #[cfg(all(feature = "std", not(feature = "tracing")))]
+        if let Some(thread) = self.thread {
+            write!(f, "thread '{}': ", thread)?;
+        }
+        if let Some(context) = self.context {
+            write!(f, "{}: ", context)?;
+        }
+        if let Some(category) = self.category {
+            write!(f, "{}: ", category)?;
+        }
+        self.error.fmt(f)?;
+        #[cfg(all(feature = "timing", not(feature = "tracing")))]
+        match self.timing {
+            Some((elapsed, Some(since_previous))) => write!(
+                f,
+                " (after {:?}, {:?} since the previous error)",
+                elapsed, since_previous
+            )?,
+            Some((elapsed, None)) => write!(f, " (after {:?})", elapsed)?,
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+// An error logged after some errors were suppressed by the rate limit
+#[cfg(all(
+    feature = "tokio",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+struct Suppressed<D> {
+    error: D,
+    suppressed: usize,
+}
+
+#[cfg(all(
+    feature = "tokio",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+impl<D> core::fmt::Display for Suppressed<D>
+where
+    D: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.suppressed {
+            0 => write!(f, "{}", self.error),
+            suppressed => write!(
+                f,
+                "{} ({} similar errors suppressed)",
+                self.error, suppressed
+            ),
+        }
+    }
+}
+
+// The error which stopped an adapter
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+struct Stopped<D>(D);
+
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+impl<D> core::fmt::Display for Stopped<D>
+where
+    D: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "stopped on: {}", self.0)
+    }
+}
+
+// Generates the methods configuring how an adapter, with a `skipper` field,
+// handles the skipped errors.
+macro_rules! skip_adapter_methods {
+    () => {
+        /// Returns the [`SkipReport`](crate::SkipReport) of the iteration so far.
+        pub fn report(&self) -> crate::SkipReport {
+            self.skipper.report
+        }
+
+        /// Returns a [`ReportHandle`](crate::ReportHandle) following the
+        /// [`SkipReport`](crate::SkipReport) of this adapter while it is
+        /// iterated.
+        ///
+        /// See [`ReportHandle`](crate::ReportHandle) for an example.
+        #[cfg(feature = "std")]
+        pub fn report_handle(&mut self) -> crate::ReportHandle {
+            let report = self.skipper.report;
+            self.skipper
+                .handle
+                .get_or_insert_with(|| crate::ReportHandle::new(report))
+                .clone()
+        }
+
+        /// Replaces each skipped error by the message returned by `redactor`,
+        /// before it is logged, sent to the sinks or recorded, so that the
+        /// sensitive data in the errors never leaves the adapter.
+        ///
+        /// ```edition2018
+        /// use skip_error::{SkipError, SkipSink, SkippedError};
+        ///
+        /// struct Messages(std::sync::mpsc::Sender<String>);
+        /// impl SkipSink for Messages {
+        ///   fn skipped(&mut self, skipped: &SkippedError<'_>) {
+        ///     let _ = self.0.send(skipped.error().to_string());
+        ///   }
+        /// }
+        ///
+        /// let (sender, receiver) = std::sync::mpsc::channel();
+        /// let tokens: Vec<u32> = vec![Ok(1), Err("invalid token secret-42")]
+        ///   .into_iter()
+        ///   .skip_error()
+        ///   .redact_with(|error| error.to_string().replace("secret-42", "***"))
+        ///   .with_sink(Messages(sender))
+        ///   .collect();
+        /// assert_eq!(receiver.iter().collect::<Vec<_>>(), vec!["invalid token ***"]);
+        /// ```
+        #[cfg(feature = "alloc")]
+        pub fn redact_with<R>(mut self, redactor: R) -> Self
+        where
+            R: Fn(&dyn core::fmt::Display) -> alloc::string::String + Send + Sync + 'static,
+        {
+            self.skipper.redactor = Some(alloc::boxed::Box::new(redactor));
+            self
+        }
+
+        /// Truncates each skipped error to its first `max_chars` characters,
+        /// followed by an ellipsis and its original length, before it is
+        /// logged, sent to the sinks or recorded (after `redact_with()`).
+        ///
+        /// ```edition2018
+        /// use skip_error::{SkipError, SkipSink, SkippedError};
+        ///
+        /// struct Messages(std::sync::mpsc::Sender<String>);
+        /// impl SkipSink for Messages {
+        ///   fn skipped(&mut self, skipped: &SkippedError<'_>) {
+        ///     let _ = self.0.send(skipped.error().to_string());
+        ///   }
+        /// }
+        ///
+        /// let (sender, receiver) = std::sync::mpsc::channel();
+        /// let body = "x".repeat(10_000);
+        /// let responses: Vec<u32> = vec![Ok(1), Err(format!("invalid body {}", body))]
+        ///   .into_iter()
+        ///   .skip_error()
+        ///   .truncate_errors(16)
+        ///   .with_sink(Messages(sender))
+        ///   .collect();
+        /// assert_eq!(
+        ///   receiver.iter().collect::<Vec<_>>(),
+        ///   vec!["invalid body xxx... (10013 characters)"]
+        /// );
+        /// ```
+        #[cfg(feature = "alloc")]
+        pub fn truncate_errors(mut self, max_chars: usize) -> Self {
+            self.skipper.max_chars = Some(max_chars);
+            self
+        }
+
+        /// Sends each skipped error to a [`SkipSink`](crate::SkipSink), in
+        /// addition to the other sinks of this adapter.
+        ///
+        /// See [`SkipSink`](crate::SkipSink) for an example.
+        #[cfg(feature = "alloc")]
+        pub fn with_sink<Sk>(mut self, sink: Sk) -> Self
+        where
+            Sk: crate::SkipSink + 'static,
+        {
+            self.skipper.sinks.push(alloc::boxed::Box::new(sink));
+            self
+        }
+
+        /// Use a specific [`ErrorFormatter`](crate::ErrorFormatter) to render
+        /// the errors logged by this adapter, instead of the one set with
+        /// [`set_error_formatter()`](crate::set_error_formatter).
+        ///
+        /// See [`ErrorFormatter`](crate::ErrorFormatter) for an example.
+        #[cfg(all(
+            feature = "std",
+            any(feature = "log", feature = "tracing", feature = "defmt")
+        ))]
+        pub fn with_formatter<Fmt>(mut self, formatter: Fmt) -> Self
+        where
+            Fmt: crate::ErrorFormatter + 'static,
+        {
+            self.skipper.formatter = Some(Box::new(formatter));
+            self
+        }
+
+        /// Tags this adapter with a `name`, to tell apart the skipped errors of
+        /// several adapters running at the same time: the name is given to
+        /// the sinks with [`SkippedError::name()`](crate::SkippedError::name),
+        /// emitted in the field `adapter` of the `tracing` events, or else
+        /// prepended to the logged errors, like `"[stop_times_import] invalid
+        /// digit found in string"`.
+        ///
+        /// ```edition2018
+        /// use skip_error::{SkipError, SkipSink, SkippedError};
+        ///
+        /// struct Names(std::sync::mpsc::Sender<String>);
+        /// impl SkipSink for Names {
+        ///   fn skipped(&mut self, skipped: &SkippedError<'_>) {
+        ///     let _ = self.0.send(skipped.name().unwrap().to_string());
+        ///   }
+        /// }
+        ///
+        /// let (sender, receiver) = std::sync::mpsc::channel();
+        /// let stop_times: Vec<u32> = ["1", "two"]
+        ///   .iter()
+        ///   .map(|string_number| string_number.parse::<u32>())
+        ///   .skip_error()
+        ///   .named("stop_times_import")
+        ///   .with_sink(Names(sender))
+        ///   .collect();
+        /// assert_eq!(receiver.iter().collect::<Vec<_>>(), vec!["stop_times_import"]);
+        /// ```
+        #[cfg(feature = "alloc")]
+        pub fn named<N>(mut self, name: N) -> Self
+        where
+            N: Into<alloc::borrow::Cow<'static, str>>,
+        {
+            self.skipper.name = Some(name.into());
+            self
+        }
+
+        /// Prepends `context` to every error logged by this adapter, like
+        /// `"loading stops: invalid digit found in string"`, to tell apart the
+        /// logs of different adapters.
+        ///
+        /// ```edition2018
+        /// use skip_error::SkipError;
+        /// # testing_logger::setup();
+        /// let stops: Vec<u32> = ["1", "two"]
+        ///   .iter()
+        ///   .map(|string_number| string_number.parse::<u32>())
+        #[cfg_attr(
+            all(feature = "log", not(feature = "tracing")),
+            doc = "  .skip_error_and_log(log::Level::Warn)"
+        )]
+        #[cfg_attr(
+            feature = "tracing",
+            doc = "  .skip_error_and_log(tracing::Level::WARN)"
+        )]
+        ///   .with_context("loading stops")
+        ///   .collect();
+        /// testing_logger::validate(|captured_logs| {
+        ///   assert_eq!(captured_logs[0].body, "loading stops: invalid digit found in string");
+        /// });
+        /// ```
+        #[cfg(all(
+            feature = "alloc",
+            any(feature = "log", feature = "tracing", feature = "defmt")
+        ))]
+        pub fn with_context<C>(mut self, context: C) -> Self
+        where
+            C: Into<alloc::borrow::Cow<'static, str>>,
+        {
+            self.skipper.context = Some(context.into());
+            self
+        }
+
+        /// Logs only once the consecutive identical errors: the first one is
+        /// logged, then a single `"previous message repeated N times"` when a
+        /// different error is logged or when the adapter is dropped.
+        ///
+        /// ```edition2018
+        /// use skip_error::SkipError;
+        /// # testing_logger::setup();
+        /// let numbers: Vec<u32> = ["one", "two", "three", "4", ""]
+        ///   .iter()
+        ///   .map(|string_number| string_number.parse::<u32>())
+        #[cfg_attr(
+            all(feature = "log", not(feature = "tracing")),
+            doc = "  .skip_error_and_log(log::Level::Warn)"
+        )]
+        #[cfg_attr(
+            feature = "tracing",
+            doc = "  .skip_error_and_log(tracing::Level::WARN)"
+        )]
+        ///   .collapse_repeated()
+        ///   .collect();
+        /// testing_logger::validate(|captured_logs| {
+        ///   let bodies: Vec<&str> = captured_logs.iter().map(|log| log.body.as_str()).collect();
+        ///   assert_eq!(
+        ///     bodies,
+        ///     vec![
+        ///       "invalid digit found in string",
+        ///       "previous message repeated 2 times",
+        ///       "cannot parse integer from empty string",
+        ///     ]
+        ///   );
+        /// });
+        /// ```
+        #[cfg(all(
+            feature = "alloc",
+            any(feature = "log", feature = "tracing", feature = "defmt")
+        ))]
+        pub fn collapse_repeated(mut self) -> Self {
+            self.skipper.repeats = Some(crate::skipper::Repeats::default());
+            self
+        }
+
+        /// Tags each logged error with the name of the thread where it was
+        /// skipped, or its id if it has no name, to tell apart the logs of
+        /// several workers: the name is emitted in the field `thread` of the
+        /// `tracing` events, or else prepended to the logged errors, like
+        /// `"thread 'worker-1': invalid digit found in string"`.
+        ///
+        /// ```edition2018
+        /// use skip_error::SkipError;
+        /// std::thread::Builder::new()
+        ///   .name("worker-1".to_string())
+        ///   .spawn(|| {
+        ///     testing_logger::setup();
+        ///     let numbers: Vec<u32> = ["1", "two"]
+        ///       .iter()
+        ///       .map(|string_number| string_number.parse::<u32>())
+        #[cfg_attr(
+            all(feature = "log", not(feature = "tracing")),
+            doc = "      .skip_error_and_log(log::Level::Warn)"
+        )]
+        #[cfg_attr(
+            feature = "tracing",
+            doc = "      .skip_error_and_log(tracing::Level::WARN)"
+        )]
+        ///       .with_thread_name()
+        ///       .collect();
+        ///     testing_logger::validate(|captured_logs| {
+        ///       assert!(captured_logs[0].body.contains("worker-1"));
+        ///     });
+        ///   })
+        ///   .unwrap()
+        ///   .join()
+        ///   .unwrap();
+        /// ```
+        #[cfg(all(
+            feature = "std",
+            any(feature = "log", feature = "tracing", feature = "defmt")
+        ))]
+        pub fn with_thread_name(mut self) -> Self {
+            self.skipper.thread_name = true;
+            self
+        }
+
+        /// Records when each error is skipped, from the call to
+        /// `with_timing()` and from the previous skipped error: the times are
+        /// emitted in the fields `elapsed` and `since_previous` of the
+        /// `tracing` events, or else appended to the logged errors, like
+        /// `"invalid digit found in string (after 12ms, 3ms since the previous
+        /// error)"`, and the times of the first and last skipped errors are
+        /// kept in the [`SkipReport`](crate::SkipReport).
+        ///
+        /// This tells apart the corruption at the start of a file from a
+        /// degradation later on.
+        ///
+        /// ```edition2018
+        /// use skip_error::SkipError;
+        /// let mut numbers = ["1", "two", "3"]
+        ///   .iter()
+        ///   .map(|string_number| string_number.parse::<u32>())
+        ///   .skip_error()
+        ///   .with_timing();
+        /// assert_eq!(numbers.report().first_skipped_after(), None);
+        /// numbers.by_ref().for_each(drop);
+        /// assert!(numbers.report().first_skipped_after().is_some());
+        /// ```
+        #[cfg(feature = "timing")]
+        pub fn with_timing(mut self) -> Self {
+            self.skipper.started = Some(std::time::Instant::now());
+            self
+        }
+
+        /// Logs the skipped errors from a dedicated thread, so a burst of
+        /// errors can't stall the iteration: each message is rendered, then
+        /// pushed without blocking into a queue of `capacity` messages. The
+        /// messages pushed while the queue is full are only counted, logged
+        /// as a single message when the adapter is dropped, which also waits
+        /// for the queued messages to be logged.
+        ///
+        /// The messages are logged outside of the spans of the iteration, and
+        /// without the fields of the event.
+        ///
+        /// ```edition2018
+        /// use skip_error::SkipError;
+        /// let numbers: Vec<u32> = ["1", "two", "3"]
+        ///   .iter()
+        ///   .map(|string_number| string_number.parse::<u32>())
+        #[cfg_attr(
+            all(feature = "log", not(feature = "tracing")),
+            doc = "  .skip_error_and_log(log::Level::Warn)"
+        )]
+        #[cfg_attr(
+            feature = "tracing",
+            doc = "  .skip_error_and_log(tracing::Level::WARN)"
+        )]
+        ///   .log_in_background(1024)
+        ///   .collect();
+        /// assert_eq!(numbers, vec![1, 3]);
+        /// ```
+        #[cfg(all(
+            feature = "std",
+            any(feature = "log", feature = "tracing", feature = "defmt")
+        ))]
+        pub fn log_in_background(mut self, capacity: usize) -> Self {
+            self.skipper.background = crate::background::BackgroundLog::spawn(capacity);
+            self
+        }
+
+        /// Logs the skipped errors in batches of `size`, each as a single
+        /// multi-line message, to reduce the pressure on the logger under
+        /// heavy error rates. The last batch is logged when the adapter is
+        /// dropped.
+        ///
+        /// ```edition2018
+        /// use skip_error::SkipError;
+        /// # testing_logger::setup();
+        /// let numbers: Vec<u32> = ["one", "two", "3", "four"]
+        ///   .iter()
+        ///   .map(|string_number| string_number.parse::<u32>())
+        #[cfg_attr(
+            all(feature = "log", not(feature = "tracing")),
+            doc = "  .skip_error_and_log(log::Level::Warn)"
+        )]
+        #[cfg_attr(
+            feature = "tracing",
+            doc = "  .skip_error_and_log(tracing::Level::WARN)"
+        )]
+        ///   .log_in_batches(2)
+        ///   .collect();
+        /// assert_eq!(numbers, vec![3]);
+        /// testing_logger::validate(|captured_logs| {
+        ///   assert_eq!(captured_logs.len(), 2);
+        ///   assert_eq!(
+        ///     captured_logs[0].body,
+        ///     "2 errors skipped:\n  invalid digit found in string\n  invalid digit found in string"
+        ///   );
+        ///   assert_eq!(captured_logs[1].body, "1 error skipped:\n  invalid digit found in string");
+        /// });
+        /// ```
+        #[cfg(all(
+            feature = "alloc",
+            any(feature = "log", feature = "tracing", feature = "defmt")
+        ))]
+        pub fn log_in_batches(mut self, size: usize) -> Self {
+            self.skipper.batch = Some(crate::skipper::Batch::new(size));
+            self
+        }
+
+        /// Logs a single message at `log_level` when the adapter is dropped if
+        /// every item was skipped, like `"all 3 items failed"`, since it is
+        /// usually a configuration error rather than bad data (see also
+        /// [`SkipReport::all_skipped()`](crate::SkipReport::all_skipped)).
+        /// This works even if the errors themselves are not logged.
+        ///
+        /// ```edition2018
+        /// use skip_error::SkipError;
+        /// # testing_logger::setup();
+        /// let numbers: Vec<u32> = ["one", "two", "three"]
+        ///   .iter()
+        ///   .map(|string_number| string_number.parse::<u32>())
+        ///   .skip_error()
+        #[cfg_attr(
+            all(feature = "log", not(feature = "tracing")),
+            doc = "  .log_if_all_skipped(log::Level::Error)"
+        )]
+        #[cfg_attr(
+            feature = "tracing",
+            doc = "  .log_if_all_skipped(tracing::Level::ERROR)"
+        )]
+        ///   .collect();
+        /// assert!(numbers.is_empty());
+        /// testing_logger::validate(|captured_logs| {
+        ///   assert_eq!(captured_logs.len(), 1);
+        ///   assert_eq!(captured_logs[0].level, log::Level::Error);
+        ///   assert_eq!(captured_logs[0].body, "all 3 items failed");
+        /// });
+        /// ```
+        #[cfg(all(
+            feature = "alloc",
+            any(feature = "log", feature = "tracing", feature = "defmt")
+        ))]
+        pub fn log_if_all_skipped<Lv>(mut self, log_level: Lv) -> Self
+        where
+            Lv: Into<crate::Level>,
+        {
+            self.skipper.all_skipped_level = Some(log_level.into());
+            self
+        }
+
+        /// Returns the level at which the skipped errors are logged, or `None`
+        /// if they are not logged.
+        #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+        pub fn log_level(&self) -> Option<crate::Level> {
+            self.skipper.log_level
+        }
+
+        /// Changes the level at which the next skipped errors are logged, or
+        /// stops logging them with `None`, without rebuilding the adapter.
+        ///
+        /// ```edition2018
+        /// use skip_error::SkipError;
+        /// # testing_logger::setup();
+        /// let mut numbers = ["one", "2", "three"]
+        ///   .iter()
+        ///   .map(|string_number| string_number.parse::<u32>())
+        #[cfg_attr(
+            all(feature = "log", not(feature = "tracing")),
+            doc = "  .skip_error_and_log(log::Level::Warn);"
+        )]
+        #[cfg_attr(
+            feature = "tracing",
+            doc = "  .skip_error_and_log(tracing::Level::WARN);"
+        )]
+        /// assert_eq!(numbers.next(), Some(2));
+        /// numbers.set_log_level(None);
+        /// assert_eq!(numbers.log_level(), None);
+        /// assert_eq!(numbers.next(), None);
+        /// testing_logger::validate(|captured_logs| assert_eq!(captured_logs.len(), 1));
+        /// ```
+        #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+        pub fn set_log_level(&mut self, log_level: Option<crate::Level>) {
+            self.skipper.log_level = log_level;
+        }
+    };
+}
+
+// Generates the methods limiting the number of errors an adapter, with a
+// `skipper` field, skips before stopping.
+macro_rules! budget_adapter_methods {
+    () => {
+        /// Allows to skip at most `budget` errors: the next error is skipped
+        /// too, but stops the adapter which then yields no more values.
+        ///
+        /// See [`TooManyErrors`](crate::TooManyErrors) for an example.
+        pub fn error_budget(mut self, budget: usize) -> Self {
+            self.skipper.budget = Some(budget);
+            self
+        }
+
+        /// Stops the adapter, which then yields no more values, as soon as
+        /// more than `threshold` (between `0.0` and `1.0`) of the last
+        /// `window` items were errors. The error rate is only checked once
+        /// `window` items were seen.
+        ///
+        /// See [`ErrorRateExceeded`](crate::ErrorRateExceeded) for an example.
+        #[cfg(feature = "alloc")]
+        pub fn abort_if_error_rate_exceeds(mut self, threshold: f64, window: usize) -> Self {
+            self.skipper.rate_window = Some(crate::skipper::RateWindow::new(threshold, window));
+            self
+        }
+
+        /// Returns why the adapter stopped, if it stopped because of the
+        /// error rate set with `abort_if_error_rate_exceeds()`.
+        #[cfg(feature = "alloc")]
+        pub fn error_rate_exceeded(&self) -> Option<crate::ErrorRateExceeded> {
+            self.skipper.error_rate_exceeded()
+        }
+    };
+}
+
+// Generates the methods limiting the rate of the logs of an adapter, with a
+// `skipper` field.
+#[cfg(all(
+    feature = "tokio",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+macro_rules! rate_limit_adapter_methods {
+    () => {
+        /// Logs at most one skipped error per `interval`, as measured by
+        /// [`tokio::time::Instant`](::tokio::time::Instant). The next logged
+        /// error mentions how many errors were suppressed in between; the
+        /// sinks still receive all the errors.
+        ///
+        /// See [`SkipErrorStreamExt`](crate::SkipErrorStreamExt) for an
+        /// example.
+        pub fn rate_limit_logs(mut self, interval: std::time::Duration) -> Self {
+            self.skipper.rate_limiter = Some(crate::tokio_support::RateLimiter::new(interval));
+            self
+        }
+    };
+}
+
+// Generates the methods attaching a `tracing::Span` to an adapter, with a
+// `skipper` field, so its skipped errors are logged inside that span.
+#[cfg(feature = "tracing")]
+macro_rules! span_adapter_methods {
+    () => {
+        /// Enters `span` whenever a skipped error is logged by this adapter,
+        /// whatever the context in which the adapter is iterated or polled.
+        pub fn in_span(mut self, span: tracing::Span) -> Self {
+            self.skipper.span = Some(span);
+            self
+        }
+
+        /// Enters the current span, at the time of this call, whenever a
+        /// skipped error is logged by this adapter (see `in_span()`).
+        pub fn in_current_span(self) -> Self {
+            self.in_span(tracing::Span::current())
+        }
+    };
+}
+
+// Generates the shortcuts of `skip_error_and_log()` for each level, like
+// `skip_error_and_warn()`, in an extension trait returning `$adapter`
+#[cfg(all(
+    any(feature = "rayon", feature = "stream"),
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+macro_rules! level_adapter_methods {
+    ($adapter:ty) => {
+        level_adapter_methods!(@level $adapter, skip_error_and_error, "error", Error, ERROR);
+        level_adapter_methods!(@level $adapter, skip_error_and_warn, "warning", Warn, WARN);
+        level_adapter_methods!(@level $adapter, skip_error_and_info, "info", Info, INFO);
+        level_adapter_methods!(@level $adapter, skip_error_and_debug, "debug", Debug, DEBUG);
+        level_adapter_methods!(@level $adapter, skip_error_and_trace, "trace", Trace, TRACE);
+    };
+    (@level $adapter:ty, $method_name:ident, $name:literal, $level:ident, $tracing_level:ident) => {
+        #[doc = concat!(
+            "Shortcut for `skip_error_and_log()` with the ",
+            $name,
+            " level, without importing the `Level` of the logger."
+        )]
+        fn $method_name(self) -> $adapter {
+            #[cfg(all(feature = "log", not(feature = "tracing")))]
+            let log_level = log::Level::$level;
+            #[cfg(feature = "tracing")]
+            let log_level = tracing::Level::$tracing_level;
+            #[cfg(all(feature = "defmt", not(any(feature = "log", feature = "tracing"))))]
+            let log_level = crate::DefmtLevel::$level;
+            self.skip_error_and_log(log_level)
+        }
+    };
+}
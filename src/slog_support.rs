@@ -0,0 +1,136 @@
+//! Emitting the skipped errors to a [`slog`](::slog) logger.
+use crate::{SkipSink, SkippedError};
+pub use slog;
+
+/// `skip_error_and_slog` returns the value of a [`Result`] or logs to a
+/// [`slog::Logger`] and continues the loop.
+///
+/// The macro takes three parameters: the [`Result`], the [`slog::Logger`] and
+/// the [`slog::Level`], which must be a constant like with the macros of
+/// `slog`. The record carries the name of the type of the error
+/// in the key `error_type`.
+/// Any [`Skippable`](crate::Skippable) type is accepted instead of the
+/// [`Result`] after `skippable:`.
+///
+/// ```edition2018
+/// # #[macro_use]
+/// # extern crate skip_error;
+/// use std::sync::{Arc, Mutex};
+///
+/// struct Collect(Arc<Mutex<Vec<String>>>);
+/// impl slog::Drain for Collect {
+///   type Ok = ();
+///   type Err = slog::Never;
+///   fn log(&self, record: &slog::Record<'_>, _: &slog::OwnedKVList) -> Result<(), slog::Never> {
+///     self.0.lock().unwrap().push(format!("{} {}", record.level(), record.msg()));
+///     Ok(())
+///   }
+/// }
+///
+/// # fn main() {
+/// let records = Arc::new(Mutex::new(Vec::new()));
+/// let logger = slog::Logger::root(Collect(Arc::clone(&records)), slog::o!());
+/// for string_number in &["1", "2", "three", "4"] {
+///   let number: u32 = skip_error_and_slog!(string_number.parse(), logger, slog::Level::Warning);
+/// }
+/// assert_eq!(*records.lock().unwrap(), vec!["WARN invalid digit found in string"]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! skip_error_and_slog {
+    (skippable: $result:expr, $logger:expr, $level:expr) => {{
+        match $crate::Skippable::into_result($result) {
+            Ok(value) => value,
+            Err(error) => {
+                $crate::__skipped!(error);
+                $crate::__slog::log!(
+                    $logger, $level, "", "{}", error;
+                    "error_type" => $crate::__type_name(&error)
+                );
+                continue;
+            }
+        }
+    }};
+    ($result:expr, $logger:expr, $level:expr) => {{
+        match $result {
+            Ok(value) => value,
+            Err(error) => {
+                $crate::__skipped!(error);
+                $crate::__slog::log!(
+                    $logger, $level, "", "{}", error;
+                    "error_type" => $crate::__type_name(&error)
+                );
+                continue;
+            }
+        }
+    }};
+}
+
+#[doc(hidden)]
+pub fn __type_name<E>(_error: &E) -> &'static str {
+    std::any::type_name::<E>()
+}
+
+/// A [`SkipSink`] emitting the skipped errors to a [`slog::Logger`], with the
+/// name of the type of the error and its position in the keys `error_type`
+/// and `index`.
+///
+/// ```edition2018
+/// use skip_error::{SkipError, SlogSink};
+/// use std::sync::{Arc, Mutex};
+///
+/// struct Collect(Arc<Mutex<Vec<String>>>);
+/// impl slog::Drain for Collect {
+///   type Ok = ();
+///   type Err = slog::Never;
+///   fn log(&self, record: &slog::Record<'_>, _: &slog::OwnedKVList) -> Result<(), slog::Never> {
+///     self.0.lock().unwrap().push(format!("{} {}", record.level(), record.msg()));
+///     Ok(())
+///   }
+/// }
+///
+/// let records = Arc::new(Mutex::new(Vec::new()));
+/// let logger = slog::Logger::root(Collect(Arc::clone(&records)), slog::o!());
+/// let numbers: Vec<u32> = ["1", "2", "three", "4"]
+///   .iter()
+///   .map(|string_number| string_number.parse())
+///   .skip_error()
+///   .with_sink(SlogSink::new(logger, slog::Level::Error))
+///   .collect();
+/// assert_eq!(numbers, vec![1, 2, 4]);
+/// assert_eq!(*records.lock().unwrap(), vec!["ERRO invalid digit found in string"]);
+/// ```
+pub struct SlogSink {
+    logger: slog::Logger,
+    level: slog::Level,
+}
+
+impl SlogSink {
+    /// Creates a sink emitting to `logger` at the given level.
+    pub fn new(logger: slog::Logger, level: slog::Level) -> Self {
+        SlogSink { logger, level }
+    }
+}
+
+impl SkipSink for SlogSink {
+    fn skipped(&mut self, skipped: &SkippedError<'_>) {
+        // The level of a `slog` record must be a constant
+        macro_rules! log {
+            ($level:expr) => {
+                slog::log!(
+                    self.logger, $level, "", "{}", skipped.error();
+                    "error_type" => skipped.type_name(),
+                    "index" => skipped.index()
+                )
+            };
+        }
+        match self.level {
+            slog::Level::Critical => log!(slog::Level::Critical),
+            slog::Level::Error => log!(slog::Level::Error),
+            slog::Level::Warning => log!(slog::Level::Warning),
+            slog::Level::Info => log!(slog::Level::Info),
+            slog::Level::Debug => log!(slog::Level::Debug),
+            slog::Level::Trace => log!(slog::Level::Trace),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Splitting a stream of [`Result`] into a stream of values and a stream of
+//! errors.
+use futures_core::Stream;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+// The state shared by both halves: each half polls the original stream when
+// its own buffer is empty, and buffers the items of the other half
+struct Split<S, T, E> {
+    inner: Pin<Box<S>>,
+    values: VecDeque<T>,
+    errors: VecDeque<E>,
+    capacity: usize,
+    done: bool,
+    values_half: Half,
+    errors_half: Half,
+}
+
+#[derive(Default)]
+struct Half {
+    waker: Option<Waker>,
+    // The items of a dropped half are not buffered anymore
+    dropped: bool,
+}
+
+impl Half {
+    fn register(&mut self, cx: &Context<'_>) {
+        self.waker = Some(cx.waker().clone());
+    }
+
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<S, T, E> Split<S, T, E>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    fn poll_value(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        loop {
+            if let Some(value) = self.values.pop_front() {
+                self.errors_half.wake();
+                return Poll::Ready(Some(value));
+            }
+            if self.done {
+                return Poll::Ready(None);
+            }
+            // Waits for the errors half to make room
+            if self.errors.len() >= self.capacity {
+                self.values_half.register(cx);
+                return Poll::Pending;
+            }
+            match self.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(value))) => return Poll::Ready(Some(value)),
+                Poll::Ready(Some(Err(error))) => {
+                    if !self.errors_half.dropped {
+                        self.errors.push_back(error);
+                        self.errors_half.wake();
+                    }
+                }
+                Poll::Ready(None) => {
+                    self.done = true;
+                    self.errors_half.wake();
+                }
+                Poll::Pending => {
+                    self.values_half.register(cx);
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+
+    fn poll_error(&mut self, cx: &mut Context<'_>) -> Poll<Option<E>> {
+        loop {
+            if let Some(error) = self.errors.pop_front() {
+                self.values_half.wake();
+                return Poll::Ready(Some(error));
+            }
+            if self.done {
+                return Poll::Ready(None);
+            }
+            // Waits for the values half to make room
+            if self.values.len() >= self.capacity {
+                self.errors_half.register(cx);
+                return Poll::Pending;
+            }
+            match self.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Err(error))) => return Poll::Ready(Some(error)),
+                Poll::Ready(Some(Ok(value))) => {
+                    if !self.values_half.dropped {
+                        self.values.push_back(value);
+                        self.values_half.wake();
+                    }
+                }
+                Poll::Ready(None) => {
+                    self.done = true;
+                    self.values_half.wake();
+                }
+                Poll::Pending => {
+                    self.errors_half.register(cx);
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+fn lock<S, T, E>(shared: &Mutex<Split<S, T, E>>) -> std::sync::MutexGuard<'_, Split<S, T, E>> {
+    shared
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub(crate) fn split<S, T, E>(inner: S, capacity: usize) -> (SplitOk<S, T, E>, SplitErr<S, T, E>) {
+    let shared = Arc::new(Mutex::new(Split {
+        inner: Box::pin(inner),
+        values: VecDeque::new(),
+        errors: VecDeque::new(),
+        capacity: capacity.max(1),
+        done: false,
+        values_half: Half::default(),
+        errors_half: Half::default(),
+    }));
+    (
+        SplitOk {
+            shared: Arc::clone(&shared),
+        },
+        SplitErr { shared },
+    )
+}
+
+/// The stream of the `Ok()` values of the original stream.
+///
+/// This `struct` is created by
+/// [`SkipErrorStreamExt::split_results()`](crate::SkipErrorStreamExt::split_results).
+pub struct SplitOk<S, T, E> {
+    shared: Arc<Mutex<Split<S, T, E>>>,
+}
+
+impl<S, T, E> Stream for SplitOk<S, T, E>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        lock(&self.shared).poll_value(cx)
+    }
+}
+
+impl<S, T, E> Drop for SplitOk<S, T, E> {
+    fn drop(&mut self) {
+        let mut split = lock(&self.shared);
+        split.values_half.dropped = true;
+        split.values.clear();
+        split.errors_half.wake();
+    }
+}
+
+/// The stream of the `Err()` values of the original stream.
+///
+/// This `struct` is created by
+/// [`SkipErrorStreamExt::split_results()`](crate::SkipErrorStreamExt::split_results).
+pub struct SplitErr<S, T, E> {
+    shared: Arc<Mutex<Split<S, T, E>>>,
+}
+
+impl<S, T, E> Stream for SplitErr<S, T, E>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    type Item = E;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<E>> {
+        lock(&self.shared).poll_error(cx)
+    }
+}
+
+impl<S, T, E> Drop for SplitErr<S, T, E> {
+    fn drop(&mut self) {
+        let mut split = lock(&self.shared);
+        split.errors_half.dropped = true;
+        split.errors.clear();
+        split.values_half.wake();
+    }
+}
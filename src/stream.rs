@@ -0,0 +1,911 @@
+//! Support for asynchronous streams of [`futures_core::Stream`].
+use crate::{skipper::Skipper, SplitErr, SplitOk, TooManyErrors};
+use futures_core::{
+    ready,
+    stream::{FusedStream, TryStream},
+    Stream,
+};
+use futures_util::{
+    io::{AsyncBufRead, AsyncBufReadExt, Lines},
+    stream::{BufferUnordered, Map, StreamExt},
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+pin_project_lite::pin_project! {
+    /// A stream that ignore errors
+    pub struct SkipErrorStream<S> {
+        #[pin]
+        inner: S,
+        skipper: Skipper,
+        stop_on_error: bool,
+        stopped: bool,
+    }
+}
+
+impl<S> SkipErrorStream<S> {
+    skip_adapter_methods!();
+    budget_adapter_methods!();
+
+    fn new(inner: S, skipper: Skipper) -> Self {
+        SkipErrorStream {
+            inner,
+            skipper,
+            stop_on_error: false,
+            stopped: false,
+        }
+    }
+
+    /// Whether the stream was ended by an error, see
+    /// [`SkipErrorStreamExt::take_while_ok()`].
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// Allows to skip at most `budget` errors like
+    /// [`error_budget()`](Self::error_budget), then yields a final
+    /// `Err(TooManyErrors)` when the next error stops the stream.
+    ///
+    /// See [`TooManyErrors`] for an example.
+    pub fn try_error_budget(self, budget: usize) -> TryErrorBudget<S> {
+        TryErrorBudget {
+            inner: self.error_budget(budget),
+            failed: false,
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<S> SkipErrorStream<S> {
+    span_adapter_methods!();
+}
+
+#[cfg(all(
+    feature = "tokio",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+impl<S> SkipErrorStream<S> {
+    rate_limit_adapter_methods!();
+}
+
+#[cfg(feature = "tokio")]
+impl<S> SkipErrorStream<S> {
+    /// Waits for `delay` after each skipped error, with
+    /// [`tokio::time::sleep()`](::tokio::time::sleep), before polling the
+    /// original stream again, so a source failing immediately and
+    /// persistently (like a closed socket) doesn't spin the task.
+    ///
+    /// ```edition2018
+    /// use skip_error::SkipErrorStreamExt;
+    /// use std::time::Duration;
+    /// use tokio_stream::StreamExt;
+    /// # #[tokio::main(flavor = "current_thread", start_paused = true)]
+    /// # async fn main() {
+    /// let start = tokio::time::Instant::now();
+    /// let numbers: Vec<u32> = tokio_stream::iter(vec!["1", "two", "three", "4"])
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error()
+    ///   .throttle_errors(Duration::from_secs(1))
+    ///   .collect()
+    ///   .await;
+    /// assert_eq!(numbers, vec![1, 4]);
+    /// assert!(start.elapsed() >= Duration::from_secs(2));
+    /// # }
+    /// ```
+    pub fn throttle_errors(
+        self,
+        delay: std::time::Duration,
+    ) -> SkipErrorStream<crate::ThrottleErrors<S>> {
+        SkipErrorStream {
+            inner: crate::ThrottleErrors::new(self.inner, delay),
+            skipper: self.skipper,
+            stop_on_error: self.stop_on_error,
+            stopped: self.stopped,
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// A stream that consumes the errors of a [`TryStream`]
+    pub struct TrySkipErrorStream<S> {
+        #[pin]
+        inner: S,
+        skipper: Skipper,
+    }
+}
+
+impl<S> TrySkipErrorStream<S> {
+    skip_adapter_methods!();
+    budget_adapter_methods!();
+}
+
+#[cfg(feature = "tracing")]
+impl<S> TrySkipErrorStream<S> {
+    span_adapter_methods!();
+}
+
+#[cfg(all(
+    feature = "tokio",
+    any(feature = "log", feature = "tracing", feature = "defmt")
+))]
+impl<S> TrySkipErrorStream<S> {
+    rate_limit_adapter_methods!();
+}
+
+impl<S, T, E> Stream for SkipErrorStream<S>
+where
+    S: Stream<Item = Result<T, E>>,
+    E: std::fmt::Display,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut this = self.project();
+        while !*this.stopped && !this.skipper.exhausted() {
+            match ready!(this.inner.as_mut().poll_next(cx)) {
+                Some(Ok(value)) => {
+                    this.skipper.ok();
+                    return Poll::Ready(Some(value));
+                }
+                Some(Err(error)) if *this.stop_on_error => {
+                    *this.stopped = true;
+                    this.skipper.stop(&error);
+                }
+                Some(Err(error)) => this.skipper.skip(&error),
+                None => return Poll::Ready(None),
+            }
+        }
+        Poll::Ready(None)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.inner.size_hint();
+        (0, upper)
+    }
+}
+
+impl<S, T, E> FusedStream for SkipErrorStream<S>
+where
+    S: FusedStream<Item = Result<T, E>>,
+    E: std::fmt::Display,
+{
+    fn is_terminated(&self) -> bool {
+        self.stopped || self.skipper.exhausted() || self.inner.is_terminated()
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// A stream that ignore errors, within a budget, and yields a final
+    /// `Err(TooManyErrors)` if the budget is exceeded
+    ///
+    /// This `struct` is created by [`SkipErrorStream::try_error_budget()`].
+    pub struct TryErrorBudget<S> {
+        #[pin]
+        inner: SkipErrorStream<S>,
+        failed: bool,
+    }
+}
+
+impl<S, T, E> Stream for TryErrorBudget<S>
+where
+    S: Stream<Item = Result<T, E>>,
+    E: std::fmt::Display,
+{
+    type Item = Result<T, TooManyErrors>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        if *this.failed {
+            return Poll::Ready(None);
+        }
+        if let Some(value) = ready!(this.inner.as_mut().poll_next(cx)) {
+            return Poll::Ready(Some(Ok(value)));
+        }
+        match this.inner.skipper.budget {
+            Some(budget) if this.inner.skipper.budget_exceeded() => {
+                *this.failed = true;
+                Poll::Ready(Some(Err(TooManyErrors::new(budget))))
+            }
+            _ => Poll::Ready(None),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.inner.size_hint();
+        (0, upper.and_then(|upper| upper.checked_add(1)))
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// A future collecting both the values and the errors of a stream
+    ///
+    /// This `struct` is created by
+    /// [`SkipErrorStreamExt::collect_ok_and_errors()`].
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct CollectOkAndErrors<S, C, E> {
+        #[pin]
+        inner: S,
+        collection: C,
+        errors: Vec<E>,
+    }
+}
+
+impl<S, C, T, E> Future for CollectOkAndErrors<S, C, E>
+where
+    S: Stream<Item = Result<T, E>>,
+    C: Default + Extend<T>,
+{
+    type Output = (C, Vec<E>);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            match ready!(this.inner.as_mut().poll_next(cx)) {
+                Some(Ok(value)) => this.collection.extend(Some(value)),
+                Some(Err(error)) => this.errors.push(error),
+                None => {
+                    return Poll::Ready((
+                        std::mem::take(this.collection),
+                        std::mem::take(this.errors),
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// Trait to extend any [`Stream`] where the [`Stream::Item`] is a [`Result`].
+/// This allows to skip errors and keep only the `Ok()` values.
+///
+/// The resulting [`SkipErrorStream`] implements `FusedStream` when the
+/// original stream does.
+///
+/// The stream adapters only depend on `futures-core` and are not tied to any
+/// runtime. For example, with the [`async-std`](https://docs.rs/async-std)
+/// runtime.
+/// ```edition2018
+/// use async_std::stream::StreamExt;
+/// use skip_error::SkipErrorStreamExt;
+/// # async_std::task::block_on(async {
+/// let mut stream = async_std::stream::from_iter(vec!["1", "2", "three", "4"])
+///   .map(|string_number| string_number.parse::<u32>())
+///   .skip_error();
+/// let mut numbers = Vec::new();
+/// while let Some(number) = stream.next().await {
+///   numbers.push(number);
+/// }
+/// assert_eq!(numbers, vec![1, 2, 4]);
+/// # });
+/// ```
+#[cfg_attr(
+    feature = "tokio",
+    doc = r##"
+For example, with the [`tokio`](::tokio) runtime and the combinators of
+`tokio_stream::StreamExt`.
+```edition2018
+use skip_error::SkipErrorStreamExt;
+use std::time::Duration;
+use tokio_stream::{wrappers::IntervalStream, Stream, StreamExt};
+# #[tokio::main(flavor = "current_thread")]
+# async fn main() {
+let interval = tokio::time::interval(Duration::from_millis(1));
+let mut tick = 0;
+let ticks = IntervalStream::new(interval)
+  .take(5)
+  .map(move |_| {
+    tick += 1;
+    if tick % 2 == 1 { Ok(tick) } else { Err("even tick") }
+  })
+  .skip_error();
+assert_eq!(ticks.size_hint(), (0, Some(5)));
+let ticks: Vec<u32> = ticks.collect().await;
+assert_eq!(ticks, vec![1, 3, 5]);
+# }
+```
+"##
+)]
+#[cfg_attr(
+    all(
+        feature = "tokio",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ),
+    doc = r##"
+When an upstream starts failing on every item, the logs can be limited to
+one per interval with `rate_limit_logs()`.
+```edition2018
+use skip_error::SkipErrorStreamExt;
+use std::time::Duration;
+use tokio_stream::StreamExt;
+# #[tokio::main(flavor = "current_thread", start_paused = true)]
+# async fn main() {
+# testing_logger::setup();
+let numbers: Vec<u32> = tokio_stream::iter(vec!["one", "two", "three", "4"])
+  .then(|string_number| async move {
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    string_number.parse::<u32>()
+  })
+"##
+)]
+#[cfg_attr(
+    all(feature = "tokio", feature = "log", not(feature = "tracing")),
+    doc = "  .skip_error_and_log(log::Level::Warn)"
+)]
+#[cfg_attr(
+    all(feature = "tokio", feature = "tracing"),
+    doc = "  .skip_error_and_log(tracing::Level::WARN)"
+)]
+#[cfg_attr(
+    all(
+        feature = "tokio",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ),
+    doc = r##"
+  .rate_limit_logs(Duration::from_secs(1))
+  .collect()
+  .await;
+assert_eq!(numbers, vec![4]);
+testing_logger::validate(|captured_logs| {
+  assert_eq!(captured_logs.len(), 2);
+  assert_eq!(captured_logs[0].body, "invalid digit found in string");
+  assert_eq!(
+    captured_logs[1].body,
+    "invalid digit found in string (1 similar errors suppressed)"
+  );
+});
+# }
+```
+"##
+)]
+#[cfg_attr(
+    all(
+        feature = "tokio",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ),
+    doc = r##"
+With `collapse_repeated()`, the repeats are collapsed before the rate limit:
+they are counted even while the logs are suppressed, and don't use the
+allowance of the interval.
+```edition2018
+use skip_error::SkipErrorStreamExt;
+use std::time::Duration;
+use tokio_stream::StreamExt;
+# #[tokio::main(flavor = "current_thread", start_paused = true)]
+# async fn main() {
+# testing_logger::setup();
+let numbers: Vec<u32> = tokio_stream::iter(vec![Err("a"), Err("a"), Err("b"), Err("c"), Err("c"), Ok(1)])
+  .then(|result| async move {
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    result
+  })
+"##
+)]
+#[cfg_attr(
+    all(feature = "tokio", feature = "log", not(feature = "tracing")),
+    doc = "  .skip_error_and_log(log::Level::Warn)"
+)]
+#[cfg_attr(
+    all(feature = "tokio", feature = "tracing"),
+    doc = "  .skip_error_and_log(tracing::Level::WARN)"
+)]
+#[cfg_attr(
+    all(
+        feature = "tokio",
+        any(feature = "log", feature = "tracing", feature = "defmt")
+    ),
+    doc = r##"
+  .collapse_repeated()
+  .rate_limit_logs(Duration::from_secs(1))
+  .collect()
+  .await;
+assert_eq!(numbers, vec![1]);
+testing_logger::validate(|captured_logs| {
+  let bodies: Vec<&str> = captured_logs.iter().map(|log| log.body.as_str()).collect();
+  assert_eq!(
+    bodies,
+    vec!["a", "previous message repeated 1 times", "b", "c (1 similar errors suppressed)"]
+  );
+});
+# }
+```
+"##
+)]
+#[cfg_attr(
+    feature = "tracing",
+    doc = r##"
+The stream is usually polled outside of the span of the task or request
+which created it, so the skipped errors can be logged inside a given span
+with `in_span()`, or inside the span current at creation with
+`in_current_span()`.
+```edition2018
+use futures::StreamExt;
+use skip_error::SkipErrorStreamExt;
+# testing_logger::setup();
+let span = tracing::info_span!("import");
+let mut numbers = futures::stream::iter(vec!["1", "2", "three", "4"])
+  .map(|string_number| string_number.parse::<u32>())
+  .skip_error_and_log(tracing::Level::WARN)
+  .in_span(span);
+# futures::executor::block_on(async {
+let numbers: Vec<u32> = numbers.collect().await;
+assert_eq!(numbers, vec![1, 2, 4]);
+# });
+testing_logger::validate(|captured_logs| {
+  let entered = captured_logs.iter().position(|log| log.body == "-> import;").unwrap();
+  let warned = captured_logs.iter().position(|log| log.level == log::Level::Warn).unwrap();
+  assert!(entered < warned);
+});
+```
+"##
+)]
+pub trait SkipErrorStreamExt<T, E>: Stream<Item = Result<T, E>> + Sized {
+    /// Skip all errors of the [`Result`] in the original [`Stream`].
+    ///
+    /// ```edition2018
+    /// use futures::StreamExt;
+    /// use skip_error::SkipErrorStreamExt;
+    /// # futures::executor::block_on(async {
+    /// let numbers: Vec<u32> = futures::stream::iter(vec!["1", "2", "three", "4"])
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .skip_error()
+    ///   .collect()
+    ///   .await;
+    /// assert_eq!(numbers, vec![1, 2, 4]);
+    /// # });
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `skip_error_and_log()`"
+        )
+    )]
+    fn skip_error(self) -> SkipErrorStream<Self> {
+        SkipErrorStream::new(self, Skipper::new())
+    }
+
+    /// Skip all errors of the [`Result`] in the original [`Stream`].  This
+    /// also allows to log the errors, choosing which level to use (see
+    /// [`SkipError::skip_error_and_log()`](crate::SkipError::skip_error_and_log)).
+    ///
+    /// ```edition2018
+    /// use futures::StreamExt;
+    /// use skip_error::SkipErrorStreamExt;
+    /// # testing_logger::setup();
+    /// # futures::executor::block_on(async {
+    /// let numbers: Vec<u32> = futures::stream::iter(vec!["1", "2", "three", "4"])
+    ///   .map(|string_number| string_number.parse::<u32>())
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .skip_error_and_log(log::Level::Warn)"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .skip_error_and_log(tracing::Level::WARN)"
+    )]
+    ///   .collect()
+    ///   .await;
+    /// assert_eq!(numbers, vec![1, 2, 4]);
+    /// # });
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs[0].level, log::Level::Warn);
+    ///   assert_eq!(captured_logs[0].body, "invalid digit found in string");
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn skip_error_and_log<L>(self, log_level: L) -> SkipErrorStream<Self>
+    where
+        L: Into<crate::Level>,
+    {
+        SkipErrorStream::new(self, Skipper::with_log_level(log_level.into()))
+    }
+
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    level_adapter_methods!(SkipErrorStream<Self>);
+
+    /// Yields the `Ok()` values of the original [`Stream`] until the first
+    /// `Err()`, which ends the stream. The error is not skipped: it is not
+    /// counted in the [`SkipReport`](crate::SkipReport) nor recorded, and
+    /// [`SkipErrorStream::is_stopped()`] tells whether the stream was ended by
+    /// an error.
+    ///
+    /// ```edition2018
+    /// use futures::StreamExt;
+    /// use skip_error::SkipErrorStreamExt;
+    /// # futures::executor::block_on(async {
+    /// let mut numbers = futures::stream::iter(vec!["1", "2", "three", "4"])
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .take_while_ok();
+    /// let collected: Vec<u32> = numbers.by_ref().collect().await;
+    /// assert_eq!(collected, vec![1, 2]);
+    /// assert!(numbers.is_stopped());
+    /// assert_eq!(numbers.report().skipped(), 0);
+    /// # });
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be dropped silently, log them with `take_while_ok_and_log()`"
+        )
+    )]
+    fn take_while_ok(self) -> SkipErrorStream<Self> {
+        SkipErrorStream {
+            stop_on_error: true,
+            ..SkipErrorStream::new(self, Skipper::new())
+        }
+    }
+
+    /// Yields the `Ok()` values of the original [`Stream`] until the first
+    /// `Err()`, which ends the stream and is logged as `"stopped on: {error}"`
+    /// (see [`SkipErrorStreamExt::take_while_ok()`]).
+    ///
+    /// ```edition2018
+    /// use futures::StreamExt;
+    /// use skip_error::SkipErrorStreamExt;
+    /// # testing_logger::setup();
+    /// # futures::executor::block_on(async {
+    /// let numbers: Vec<u32> = futures::stream::iter(vec!["1", "2", "three", "4"])
+    ///   .map(|string_number| string_number.parse::<u32>())
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .take_while_ok_and_log(log::Level::Error)"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .take_while_ok_and_log(tracing::Level::ERROR)"
+    )]
+    ///   .collect()
+    ///   .await;
+    /// assert_eq!(numbers, vec![1, 2]);
+    /// # });
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs.len(), 1);
+    ///   assert_eq!(captured_logs[0].level, log::Level::Error);
+    ///   assert_eq!(captured_logs[0].body, "stopped on: invalid digit found in string");
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn take_while_ok_and_log<L>(self, log_level: L) -> SkipErrorStream<Self>
+    where
+        L: Into<crate::Level>,
+    {
+        SkipErrorStream {
+            stop_on_error: true,
+            ..SkipErrorStream::new(self, Skipper::with_log_level(log_level.into()))
+        }
+    }
+
+    /// Drives the original [`Stream`] to completion, collecting the `Ok()`
+    /// values into a collection and keeping all the `Err()` values, so the
+    /// errors can still be reported once the stream is over.
+    ///
+    /// ```edition2018
+    /// use futures::StreamExt;
+    /// use skip_error::SkipErrorStreamExt;
+    /// # futures::executor::block_on(async {
+    /// let (numbers, errors): (Vec<u32>, _) = futures::stream::iter(vec!["1", "2", "three", "4"])
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .collect_ok_and_errors()
+    ///   .await;
+    /// assert_eq!(numbers, vec![1, 2, 4]);
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors[0].to_string(), "invalid digit found in string");
+    /// # });
+    /// ```
+    fn collect_ok_and_errors<C>(self) -> CollectOkAndErrors<Self, C, E>
+    where
+        C: Default + Extend<T>,
+    {
+        CollectOkAndErrors {
+            inner: self,
+            collection: C::default(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Splits the original [`Stream`] into a stream of the `Ok()` values and
+    /// a stream of the `Err()` values, which can be consumed concurrently,
+    /// like the values by the processing and the errors by a reporting task.
+    ///
+    /// Each stream polls the original stream when it has nothing to yield,
+    /// buffering at most `capacity` items of the other stream: it then waits
+    /// for the other stream to be consumed. Once one of the streams is
+    /// dropped, the items of this stream are dropped instead of buffered.
+    ///
+    /// ```edition2018
+    /// use futures::StreamExt;
+    /// use skip_error::SkipErrorStreamExt;
+    /// # futures::executor::block_on(async {
+    /// let (numbers, errors) = futures::stream::iter(vec!["1", "two", "three", "4", "five"])
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .split_results(1);
+    /// let (numbers, errors): (Vec<u32>, Vec<_>) = futures::join!(
+    ///   numbers.collect(),
+    ///   errors.map(|error| error.to_string()).collect(),
+    /// );
+    /// assert_eq!(numbers, vec![1, 4]);
+    /// assert_eq!(errors.len(), 3);
+    /// # });
+    /// ```
+    fn split_results(self, capacity: usize) -> (SplitOk<Self, T, E>, SplitErr<Self, T, E>) {
+        crate::split::split(self, capacity)
+    }
+}
+
+impl<S, T, E> SkipErrorStreamExt<T, E> for S where S: Stream<Item = Result<T, E>> {}
+
+impl<S> Stream for TrySkipErrorStream<S>
+where
+    S: TryStream,
+    S::Error: std::fmt::Display,
+{
+    type Item = S::Ok;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Ok>> {
+        let mut this = self.project();
+        while !this.skipper.exhausted() {
+            match ready!(this.inner.as_mut().try_poll_next(cx)) {
+                Some(Ok(value)) => {
+                    this.skipper.ok();
+                    return Poll::Ready(Some(value));
+                }
+                Some(Err(error)) => this.skipper.skip(&error),
+                None => return Poll::Ready(None),
+            }
+        }
+        Poll::Ready(None)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.inner.size_hint();
+        (0, upper)
+    }
+}
+
+impl<S> FusedStream for TrySkipErrorStream<S>
+where
+    S: TryStream + FusedStream,
+    S::Error: std::fmt::Display,
+{
+    fn is_terminated(&self) -> bool {
+        self.skipper.exhausted() || self.inner.is_terminated()
+    }
+}
+
+/// Trait to extend any [`TryStream`], consuming its error channel.
+/// This yields an infallible [`Stream`] of the `Ok()` values, which composes
+/// with the rest of the `StreamExt` combinators.
+pub trait TrySkipErrorStreamExt: TryStream + Sized {
+    /// Skip all errors of the original [`TryStream`].
+    ///
+    /// ```edition2018
+    /// use futures::{StreamExt, TryStreamExt};
+    /// use skip_error::TrySkipErrorStreamExt;
+    /// # futures::executor::block_on(async {
+    /// let numbers: Vec<u32> = futures::stream::iter(vec!["1", "2", "three", "4"])
+    ///   .map(|string_number| string_number.parse::<u32>())
+    ///   .map_ok(|number| number * 10)
+    ///   .try_skip_error()
+    ///   .map(|number| number + 1)
+    ///   .collect()
+    ///   .await;
+    /// assert_eq!(numbers, vec![11, 21, 41]);
+    /// # });
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `try_skip_error_and_log()`"
+        )
+    )]
+    fn try_skip_error(self) -> TrySkipErrorStream<Self> {
+        TrySkipErrorStream {
+            inner: self,
+            skipper: Skipper::new(),
+        }
+    }
+
+    /// Skip all errors of the original [`TryStream`], logging them (see
+    /// [`SkipErrorStreamExt::skip_error_and_log()`]).
+    ///
+    /// ```edition2018
+    /// use futures::{StreamExt, TryStreamExt};
+    /// use skip_error::TrySkipErrorStreamExt;
+    /// # testing_logger::setup();
+    /// # futures::executor::block_on(async {
+    /// let numbers: Vec<u32> = futures::stream::iter(vec!["1", "2", "three", "4"])
+    ///   .map(|string_number| string_number.parse::<u32>())
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .try_skip_error_and_log(log::Level::Warn)"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .try_skip_error_and_log(tracing::Level::WARN)"
+    )]
+    ///   .collect()
+    ///   .await;
+    /// assert_eq!(numbers, vec![1, 2, 4]);
+    /// # });
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs[0].level, log::Level::Warn);
+    ///   assert_eq!(captured_logs[0].body, "invalid digit found in string");
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn try_skip_error_and_log<L>(self, log_level: L) -> TrySkipErrorStream<Self>
+    where
+        L: Into<crate::Level>,
+    {
+        TrySkipErrorStream {
+            inner: self,
+            skipper: Skipper::with_log_level(log_level.into()),
+        }
+    }
+}
+
+impl<S> TrySkipErrorStreamExt for S where S: TryStream {}
+
+/// The stream of the results of the concurrent asynchronous functions.
+///
+/// This type is used by [`MapConcurrentStreamExt::map_concurrent_skip_error()`].
+pub type MapConcurrent<S, F> = BufferUnordered<Map<S, F>>;
+
+/// Trait to extend any [`Stream`] with fallible asynchronous functions which
+/// run concurrently, skipping their errors.
+pub trait MapConcurrentStreamExt: Stream + Sized {
+    /// Applies `f` on each item, running up to `limit` of the resulting
+    /// futures concurrently, and yields their `Ok()` values in the order of
+    /// completion, skipping the `Err()` values.
+    ///
+    /// ```edition2018
+    /// use futures::StreamExt;
+    /// use skip_error::MapConcurrentStreamExt;
+    /// # futures::executor::block_on(async {
+    /// let mut numbers: Vec<u32> = futures::stream::iter(vec!["1", "2", "three", "4"])
+    ///   .map_concurrent_skip_error(2, |string_number| async move {
+    ///     string_number.parse::<u32>()
+    ///   })
+    ///   .collect()
+    ///   .await;
+    /// numbers.sort();
+    /// assert_eq!(numbers, vec![1, 2, 4]);
+    /// # });
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `map_concurrent_skip_error_and_log()`"
+        )
+    )]
+    #[allow(deprecated)]
+    fn map_concurrent_skip_error<F, Fut, T, E>(
+        self,
+        limit: usize,
+        f: F,
+    ) -> SkipErrorStream<MapConcurrent<Self, F>>
+    where
+        F: FnMut(Self::Item) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+    {
+        self.map(f).buffer_unordered(limit).skip_error()
+    }
+
+    /// Applies `f` on each item like
+    /// [`MapConcurrentStreamExt::map_concurrent_skip_error()`], and logs the
+    /// skipped errors (see [`SkipErrorStreamExt::skip_error_and_log()`]).
+    ///
+    /// ```edition2018
+    /// use futures::StreamExt;
+    /// use skip_error::MapConcurrentStreamExt;
+    /// # testing_logger::setup();
+    /// # futures::executor::block_on(async {
+    /// let numbers: Vec<u32> = futures::stream::iter(vec!["1", "2", "three", "4"])
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .map_concurrent_skip_error_and_log(2, log::Level::Warn, |string_number| async move {"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .map_concurrent_skip_error_and_log(2, tracing::Level::WARN, |string_number| async move {"
+    )]
+    ///     string_number.parse::<u32>()
+    ///   })
+    ///   .collect()
+    ///   .await;
+    /// assert_eq!(numbers.len(), 3);
+    /// # });
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs[0].level, log::Level::Warn);
+    ///   assert_eq!(captured_logs[0].body, "invalid digit found in string");
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn map_concurrent_skip_error_and_log<L, F, Fut, T, E>(
+        self,
+        limit: usize,
+        log_level: L,
+        f: F,
+    ) -> SkipErrorStream<MapConcurrent<Self, F>>
+    where
+        L: Into<crate::Level>,
+        F: FnMut(Self::Item) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+    {
+        self.map(f)
+            .buffer_unordered(limit)
+            .skip_error_and_log(log_level)
+    }
+}
+
+impl<S> MapConcurrentStreamExt for S where S: Stream {}
+
+/// Trait to extend any [`futures_util::io::AsyncBufRead`] with a stream of
+/// its lines, skipping the lines which could not be read (like an I/O error
+/// or invalid UTF-8).
+///
+/// Note that a reader failing on every read never ends, which can be
+/// prevented with [`SkipErrorStream::error_budget()`].
+pub trait SkipErrorAsyncBufReadExt: AsyncBufRead + Sized {
+    /// Returns a stream of the lines of this reader, skipping the errors.
+    ///
+    /// ```edition2018
+    /// use futures::StreamExt;
+    /// use skip_error::SkipErrorAsyncBufReadExt;
+    /// # futures::executor::block_on(async {
+    /// let reader = futures::io::Cursor::new(b"one\ntw\xF0o\nthree\n");
+    /// let lines: Vec<String> = reader.lines_skip_error().collect().await;
+    /// assert_eq!(lines, vec!["one", "three"]);
+    /// # });
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `lines_skip_error_and_log()`"
+        )
+    )]
+    #[allow(deprecated)]
+    fn lines_skip_error(self) -> SkipErrorStream<Lines<Self>> {
+        self.lines().skip_error()
+    }
+
+    /// Returns a stream of the lines of this reader, logging and skipping
+    /// the errors (see [`SkipErrorStreamExt::skip_error_and_log()`]).
+    ///
+    /// ```edition2018
+    /// use futures::StreamExt;
+    /// use skip_error::SkipErrorAsyncBufReadExt;
+    /// # testing_logger::setup();
+    /// # futures::executor::block_on(async {
+    /// let reader = futures::io::Cursor::new(b"one\ntw\xF0o\nthree\n");
+    /// let lines: Vec<String> = reader
+    #[cfg_attr(
+        all(feature = "log", not(feature = "tracing")),
+        doc = "  .lines_skip_error_and_log(log::Level::Warn)"
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        doc = "  .lines_skip_error_and_log(tracing::Level::WARN)"
+    )]
+    ///   .collect()
+    ///   .await;
+    /// assert_eq!(lines, vec!["one", "three"]);
+    /// # });
+    /// testing_logger::validate(|captured_logs| {
+    ///   assert_eq!(captured_logs[0].level, log::Level::Warn);
+    ///   assert!(captured_logs[0].body.contains("UTF-8"));
+    /// });
+    /// ```
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn lines_skip_error_and_log<L>(self, log_level: L) -> SkipErrorStream<Lines<Self>>
+    where
+        L: Into<crate::Level>,
+    {
+        self.lines().skip_error_and_log(log_level)
+    }
+}
+
+impl<R> SkipErrorAsyncBufReadExt for R where R: AsyncBufRead {}
@@ -0,0 +1,107 @@
+//! Sending the skipped errors to the system logger.
+use crate::{SkipSink, SkippedError};
+use std::{io, os::unix::net::UnixDatagram, path::Path};
+
+/// The severity of the messages sent by a [`SyslogSink`], as defined by
+/// [RFC 5424](https://datatracker.ietf.org/doc/html/rfc5424#section-6.2.1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SyslogSeverity {
+    /// The system is unusable (0).
+    Emergency,
+    /// Action must be taken immediately (1).
+    Alert,
+    /// Critical conditions (2).
+    Critical,
+    /// Error conditions (3).
+    Error,
+    /// Warning conditions (4).
+    Warning,
+    /// Normal but significant condition (5).
+    Notice,
+    /// Informational messages (6).
+    Informational,
+    /// Debug-level messages (7).
+    Debug,
+}
+
+/// A [`SkipSink`] sending one message per skipped error to the local syslog
+/// daemon, or to systemd-journald which also listens on `/dev/log`.
+///
+/// The messages are sent with the facility `user` and the given severity.
+/// Each message is `<identifier>: item <index>: <error>`, or only the error
+/// after the identifier if the index is unknown. Errors while sending are
+/// ignored.
+///
+/// ```edition2018
+/// use skip_error::{SkipError, SyslogSeverity, SyslogSink};
+/// use std::os::unix::net::UnixDatagram;
+/// let path = std::env::temp_dir().join("skip_error_syslog_example.sock");
+/// # let _ = std::fs::remove_file(&path);
+/// let syslog = UnixDatagram::bind(&path).unwrap();
+/// let numbers: Vec<u32> = ["1", "two", "3"]
+///   .iter()
+///   .map(|string_number| string_number.parse())
+///   .skip_error()
+///   .with_sink(SyslogSink::connect(&path, "import", SyslogSeverity::Warning).unwrap())
+///   .collect();
+/// assert_eq!(numbers, vec![1, 3]);
+/// let mut message = [0; 128];
+/// let len = syslog.recv(&mut message).unwrap();
+/// assert_eq!(&message[..len], b"<12>import: item 1: invalid digit found in string");
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct SyslogSink {
+    socket: UnixDatagram,
+    identifier: String,
+    priority: u8,
+}
+
+impl SyslogSink {
+    /// Creates a sink sending to the local syslog daemon on `/dev/log`, with
+    /// the `identifier` (usually the name of the program) and the `severity`.
+    pub fn new<I>(identifier: I, severity: SyslogSeverity) -> io::Result<Self>
+    where
+        I: Into<String>,
+    {
+        SyslogSink::connect("/dev/log", identifier, severity)
+    }
+
+    /// Creates a sink sending to the datagram socket at `path`, see
+    /// [`SyslogSink::new()`].
+    pub fn connect<P, I>(path: P, identifier: I, severity: SyslogSeverity) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+        I: Into<String>,
+    {
+        let socket = UnixDatagram::unbound()?;
+        socket.connect(path)?;
+        // The facility `user` is 1
+        let priority = 8 + severity as u8;
+        Ok(SyslogSink {
+            socket,
+            identifier: identifier.into(),
+            priority,
+        })
+    }
+}
+
+impl SkipSink for SyslogSink {
+    fn skipped(&mut self, skipped: &SkippedError<'_>) {
+        let message = match skipped.index() {
+            Some(index) => format!(
+                "<{}>{}: item {}: {}",
+                self.priority,
+                self.identifier,
+                index,
+                skipped.error()
+            ),
+            None => format!(
+                "<{}>{}: {}",
+                self.priority,
+                self.identifier,
+                skipped.error()
+            ),
+        };
+        let _ = self.socket.send(message.as_bytes());
+    }
+}
@@ -0,0 +1,80 @@
+//! Helpers to assert on skipped errors in tests, without depending on a log
+//! backend.
+use std::cell::RefCell;
+
+thread_local! {
+    static CAPTURES: RefCell<Vec<Vec<String>>> = const { RefCell::new(Vec::new()) };
+}
+
+pub(crate) fn record(error: &dyn std::fmt::Display) {
+    CAPTURES.with(|captures| {
+        if let Some(capture) = captures.borrow_mut().last_mut() {
+            capture.push(error.to_string());
+        }
+    });
+}
+
+/// The errors skipped during a call to [`capture()`], rendered with
+/// [`std::fmt::Display`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Captured {
+    errors: Vec<String>,
+}
+
+impl Captured {
+    /// Returns the captured errors.
+    pub fn into_inner(self) -> Vec<String> {
+        self.errors
+    }
+}
+
+impl std::ops::Deref for Captured {
+    type Target = [String];
+
+    fn deref(&self) -> &[String] {
+        &self.errors
+    }
+}
+
+// Stops the innermost capture, even if the captured closure panics
+struct CaptureGuard;
+
+impl Drop for CaptureGuard {
+    fn drop(&mut self) {
+        CAPTURES.with(|captures| {
+            captures.borrow_mut().pop();
+        });
+    }
+}
+
+/// Runs the closure and captures all the errors skipped on the current thread
+/// while it runs, either with one of the macros or with one of the adapters.
+///
+/// When captures are nested, errors are only captured by the innermost one.
+///
+/// ```edition2018
+/// use skip_error::SkipError;
+/// let (numbers, skipped) = skip_error::test_util::capture(|| {
+///   ["1", "two", "3", "four"]
+///     .iter()
+///     .map(|string_number| string_number.parse::<u32>())
+///     .skip_error()
+///     .collect::<Vec<_>>()
+/// });
+/// assert_eq!(numbers, vec![1, 3]);
+/// assert_eq!(skipped.len(), 2);
+/// assert!(skipped[0].contains("invalid digit"));
+/// ```
+pub fn capture<F, R>(f: F) -> (R, Captured)
+where
+    F: FnOnce() -> R,
+{
+    CAPTURES.with(|captures| captures.borrow_mut().push(Vec::new()));
+    let guard = CaptureGuard;
+    let result = f();
+    let errors = CAPTURES
+        .with(|captures| captures.borrow_mut().last_mut().map(std::mem::take))
+        .unwrap_or_default();
+    drop(guard);
+    (result, Captured { errors })
+}
@@ -0,0 +1,858 @@
+//! Integration with the [`tokio`](::tokio) runtime.
+//!
+//! [`SkipErrorStream`] implements [`futures_core::Stream`], which is the
+//! `Stream` trait used by `tokio_stream`: it composes with the combinators of
+//! `tokio_stream::StreamExt` and with the wrappers of `tokio_stream::wrappers`.
+use crate::{
+    fs::PathError, skipper::Skipper, Backoff, Classifier, OwnedSkippedError, RetryPolicy,
+    SkipErrorStream, SkipErrorStreamExt, SkipSink, SkippedError,
+};
+use futures_core::{ready, Stream};
+use std::{
+    future::Future,
+    io,
+    path::PathBuf,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+use tokio::{
+    io::{AsyncBufRead, AsyncBufReadExt},
+    sync::mpsc::Sender,
+    task::{JoinError, JoinSet},
+    time::Sleep,
+};
+use tokio_stream::wrappers::{LinesStream, ReadDirStream, ReceiverStream, UnboundedReceiverStream};
+
+/// Trait to extend the receivers of the [`tokio::sync::mpsc`](::tokio::sync::mpsc)
+/// channels carrying [`Result`], so they can skip errors like any other
+/// [`Stream`].
+///
+/// ```edition2018
+/// use skip_error::SkipErrorReceiverExt;
+/// use tokio_stream::StreamExt;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let (sender, receiver) = tokio::sync::mpsc::channel(4);
+/// tokio::spawn(async move {
+///   for string_number in &["1", "2", "three", "4"] {
+///     sender.send(string_number.parse::<u32>()).await.unwrap();
+///   }
+/// });
+/// let numbers: Vec<u32> = receiver.skip_error().collect().await;
+/// assert_eq!(numbers, vec![1, 2, 4]);
+/// # }
+/// ```
+pub trait SkipErrorReceiverExt<T, E>: Sized {
+    /// The [`Stream`] wrapping the receiver.
+    type Stream: Stream<Item = Result<T, E>>;
+
+    /// Wraps the receiver into a [`Stream`].
+    fn into_stream(self) -> Self::Stream;
+
+    /// Skip all errors received from the channel.
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `skip_error_and_log()`"
+        )
+    )]
+    #[allow(deprecated)]
+    fn skip_error(self) -> SkipErrorStream<Self::Stream> {
+        self.into_stream().skip_error()
+    }
+
+    /// Skip all errors received from the channel, logging them (see
+    /// [`SkipErrorStreamExt::skip_error_and_log()`]).
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn skip_error_and_log<L>(self, log_level: L) -> SkipErrorStream<Self::Stream>
+    where
+        L: Into<crate::Level>,
+    {
+        self.into_stream().skip_error_and_log(log_level)
+    }
+}
+
+impl<T, E> SkipErrorReceiverExt<T, E> for tokio::sync::mpsc::Receiver<Result<T, E>> {
+    type Stream = ReceiverStream<Result<T, E>>;
+
+    fn into_stream(self) -> Self::Stream {
+        ReceiverStream::new(self)
+    }
+}
+
+impl<T, E> SkipErrorReceiverExt<T, E> for tokio::sync::mpsc::UnboundedReceiver<Result<T, E>> {
+    type Stream = UnboundedReceiverStream<Result<T, E>>;
+
+    fn into_stream(self) -> Self::Stream {
+        UnboundedReceiverStream::new(self)
+    }
+}
+
+/// The error of a task spawned in a [`JoinSet`]: either the task failed to
+/// complete (it panicked or was cancelled), or it returned an `Err()`.
+#[derive(Debug)]
+pub enum TaskError<E> {
+    /// The task panicked or was cancelled.
+    Join(JoinError),
+    /// The task returned an error.
+    Failed(E),
+}
+
+impl<E> std::fmt::Display for TaskError<E>
+where
+    E: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskError::Join(error) => error.fmt(f),
+            TaskError::Failed(error) => error.fmt(f),
+        }
+    }
+}
+
+impl<E> std::error::Error for TaskError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TaskError::Join(error) => Some(error),
+            TaskError::Failed(error) => Some(error),
+        }
+    }
+}
+
+/// A stream of the outputs of the tasks of a [`JoinSet`], in the order of
+/// their completion.
+///
+/// This `struct` is created by [`SkipErrorJoinSetExt::into_stream()`].
+pub struct JoinSetStream<T, E> {
+    join_set: JoinSet<Result<T, E>>,
+}
+
+impl<T, E> Stream for JoinSetStream<T, E>
+where
+    T: 'static,
+    E: 'static,
+{
+    type Item = Result<T, TaskError<E>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let output = ready!(self.get_mut().join_set.poll_join_next(cx));
+        Poll::Ready(output.map(|output| match output {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(error)) => Err(TaskError::Failed(error)),
+            Err(error) => Err(TaskError::Join(error)),
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.join_set.len(), Some(self.join_set.len()))
+    }
+}
+
+/// Trait to extend a [`JoinSet`] of fallible tasks, so the tasks which
+/// panicked, were cancelled or returned an `Err()` are skipped, yielding only
+/// the successful values.
+///
+/// ```edition2018
+/// use skip_error::SkipErrorJoinSetExt;
+/// use tokio_stream::StreamExt;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let mut join_set = tokio::task::JoinSet::new();
+/// for string_number in vec!["1", "2", "three", "4"] {
+///   join_set.spawn(async move { string_number.parse::<u32>() });
+/// }
+/// join_set.spawn(async { panic!("task failed") });
+/// let mut numbers = join_set.skip_error();
+/// let mut collected = Vec::new();
+/// while let Some(number) = numbers.next().await {
+///   collected.push(number);
+/// }
+/// collected.sort();
+/// assert_eq!(collected, vec![1, 2, 4]);
+/// assert_eq!(numbers.report().skipped(), 2);
+/// # }
+/// ```
+pub trait SkipErrorJoinSetExt<T, E>: Sized
+where
+    T: 'static,
+    E: 'static,
+{
+    /// Wraps the [`JoinSet`] into a [`Stream`].
+    fn into_stream(self) -> JoinSetStream<T, E>;
+
+    /// Skip all the failed tasks of the [`JoinSet`].
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `skip_error_and_log()`"
+        )
+    )]
+    #[allow(deprecated)]
+    fn skip_error(self) -> SkipErrorStream<JoinSetStream<T, E>> {
+        self.into_stream().skip_error()
+    }
+
+    /// Skip all the failed tasks of the [`JoinSet`], logging their errors
+    /// (see [`SkipErrorStreamExt::skip_error_and_log()`]).
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn skip_error_and_log<L>(self, log_level: L) -> SkipErrorStream<JoinSetStream<T, E>>
+    where
+        L: Into<crate::Level>,
+    {
+        self.into_stream().skip_error_and_log(log_level)
+    }
+}
+
+impl<T, E> SkipErrorJoinSetExt<T, E> for JoinSet<Result<T, E>>
+where
+    T: 'static,
+    E: 'static,
+{
+    fn into_stream(self) -> JoinSetStream<T, E> {
+        JoinSetStream { join_set: self }
+    }
+}
+
+/// Trait to extend any [`tokio::io::AsyncBufRead`](::tokio::io::AsyncBufRead)
+/// with a stream of its lines, skipping the lines which could not be read
+/// (see [`SkipErrorAsyncBufReadExt`](crate::SkipErrorAsyncBufReadExt)).
+///
+/// ```edition2018
+/// use skip_error::SkipErrorTokioBufReadExt;
+/// use tokio_stream::StreamExt;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let reader: &[u8] = b"one\ntw\xF0o\nthree\n";
+/// let lines: Vec<String> = reader.lines_skip_error().collect().await;
+/// assert_eq!(lines, vec!["one", "three"]);
+/// # }
+/// ```
+pub trait SkipErrorTokioBufReadExt: AsyncBufRead + Sized {
+    /// Returns a stream of the lines of this reader, skipping the errors.
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `lines_skip_error_and_log()`"
+        )
+    )]
+    #[allow(deprecated)]
+    fn lines_skip_error(self) -> SkipErrorStream<LinesStream<Self>> {
+        LinesStream::new(self.lines()).skip_error()
+    }
+
+    /// Returns a stream of the lines of this reader, logging and skipping
+    /// the errors (see [`SkipErrorStreamExt::skip_error_and_log()`]).
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    fn lines_skip_error_and_log<L>(self, log_level: L) -> SkipErrorStream<LinesStream<Self>>
+    where
+        L: Into<crate::Level>,
+    {
+        LinesStream::new(self.lines()).skip_error_and_log(log_level)
+    }
+}
+
+impl<R> SkipErrorTokioBufReadExt for R where R: AsyncBufRead {}
+
+/// Returns a stream of the entries of a directory, with
+/// [`tokio::fs::read_dir()`](::tokio::fs::read_dir), skipping the entries
+/// which could not be read (like a permission denied, or a file deleted during
+/// the scan).
+///
+/// Only the errors of the entries are skipped: an error is returned if the
+/// directory itself cannot be read.
+///
+/// ```edition2018
+/// use tokio_stream::StreamExt;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> std::io::Result<()> {
+/// let directory = std::env::temp_dir().join("skip_error_read_dir");
+/// # let _ = std::fs::remove_dir_all(&directory);
+/// std::fs::create_dir_all(&directory)?;
+/// std::fs::write(directory.join("stops.txt"), "")?;
+/// let entries: Vec<_> = skip_error::read_dir_skip_error(&directory).await?.collect().await;
+/// assert_eq!(entries.len(), 1);
+/// assert_eq!(entries[0].file_name(), "stops.txt");
+/// # std::fs::remove_dir_all(&directory)?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(
+    feature = "forbid-silent",
+    deprecated(
+        note = "the errors must not be skipped silently, log them with `read_dir_skip_error_and_log()`"
+    )
+)]
+#[allow(deprecated)]
+pub async fn read_dir_skip_error<P>(path: P) -> std::io::Result<SkipErrorStream<ReadDirStream>>
+where
+    P: AsRef<std::path::Path>,
+{
+    let read_dir = tokio::fs::read_dir(path).await?;
+    Ok(ReadDirStream::new(read_dir).skip_error())
+}
+
+/// Returns a stream of the entries of a directory like
+/// [`read_dir_skip_error()`], logging the skipped errors (see
+/// [`SkipErrorStreamExt::skip_error_and_log()`]).
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+pub async fn read_dir_skip_error_and_log<P, L>(
+    path: P,
+    log_level: L,
+) -> std::io::Result<SkipErrorStream<ReadDirStream>>
+where
+    P: AsRef<std::path::Path>,
+    L: Into<crate::Level>,
+{
+    let read_dir = tokio::fs::read_dir(path).await?;
+    Ok(ReadDirStream::new(read_dir).skip_error_and_log(log_level))
+}
+
+// Iterates `iter` on a thread of the blocking pool, sending the items into a
+// channel of `capacity` items, until the receiver is dropped
+fn spawn_blocking<I, T, E>(iter: I, capacity: usize) -> ReceiverStream<Result<T, E>>
+where
+    I: IntoIterator<Item = Result<T, E>> + Send + 'static,
+    T: Send + 'static,
+    E: Send + 'static,
+{
+    let (sender, receiver) = tokio::sync::mpsc::channel(capacity.max(1));
+    tokio::task::spawn_blocking(move || {
+        for item in iter {
+            if sender.blocking_send(item).is_err() {
+                break;
+            }
+        }
+    });
+    ReceiverStream::new(receiver)
+}
+
+/// Iterates a blocking iterator of [`Result`], like a synchronous parser, on
+/// a thread of the blocking pool of [`tokio`](::tokio) with
+/// [`tokio::task::spawn_blocking()`](::tokio::task::spawn_blocking), and
+/// returns a stream of its values, skipping the errors.
+///
+/// At most `capacity` items are buffered: the iteration waits for the stream
+/// to be consumed, and stops once the stream is dropped.
+///
+/// ```edition2018
+/// use tokio_stream::StreamExt;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let lines = vec!["1", "two", "3"].into_iter().map(str::parse::<u32>);
+/// let numbers: Vec<u32> = skip_error::spawn_blocking_skip_error(lines, 16).collect().await;
+/// assert_eq!(numbers, vec![1, 3]);
+/// # }
+/// ```
+#[cfg_attr(
+    feature = "forbid-silent",
+    deprecated(
+        note = "the errors must not be skipped silently, log them with `spawn_blocking_skip_error_and_log()`"
+    )
+)]
+#[allow(deprecated)]
+pub fn spawn_blocking_skip_error<I, T, E>(
+    iter: I,
+    capacity: usize,
+) -> SkipErrorStream<ReceiverStream<Result<T, E>>>
+where
+    I: IntoIterator<Item = Result<T, E>> + Send + 'static,
+    T: Send + 'static,
+    E: Send + 'static,
+{
+    spawn_blocking(iter, capacity).skip_error()
+}
+
+/// Like [`spawn_blocking_skip_error()`], logging the skipped errors (see
+/// [`SkipErrorStreamExt::skip_error_and_log()`]). The errors are logged by
+/// the task consuming the stream, not by the blocking thread.
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+pub fn spawn_blocking_skip_error_and_log<I, T, E, L>(
+    iter: I,
+    capacity: usize,
+    log_level: L,
+) -> SkipErrorStream<ReceiverStream<Result<T, E>>>
+where
+    I: IntoIterator<Item = Result<T, E>> + Send + 'static,
+    T: Send + 'static,
+    E: Send + 'static,
+    L: Into<crate::Level>,
+{
+    spawn_blocking(iter, capacity).skip_error_and_log(log_level)
+}
+
+/// Returns a stream of the entries of a directory and of all its
+/// subdirectories, depth first, skipping the entries which could not be read
+/// and the content of the subdirectories which could not be opened, like
+/// [`fs::walk_dir_skipping()`](crate::fs::walk_dir_skipping).
+///
+/// ```edition2018
+/// use tokio_stream::StreamExt;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> std::io::Result<()> {
+/// let directory = std::env::temp_dir().join("skip_error_walk_dir");
+/// # let _ = std::fs::remove_dir_all(&directory);
+/// std::fs::create_dir_all(directory.join("stops/2024"))?;
+/// std::fs::write(directory.join("stops/2024/stops.txt"), "")?;
+/// let mut names: Vec<_> = skip_error::walk_dir_skip_error(&directory)
+///   .await?
+///   .map(|entry| entry.file_name())
+///   .collect()
+///   .await;
+/// names.sort();
+/// assert_eq!(names, vec!["2024", "stops", "stops.txt"]);
+/// # std::fs::remove_dir_all(&directory)?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(
+    feature = "forbid-silent",
+    deprecated(
+        note = "the errors must not be skipped silently, log them with `walk_dir_skip_error_and_log()`"
+    )
+)]
+pub async fn walk_dir_skip_error<P>(path: P) -> std::io::Result<WalkDirStream>
+where
+    P: AsRef<std::path::Path>,
+{
+    WalkDirStream::new(path.as_ref(), Skipper::new()).await
+}
+
+/// Returns a stream of the entries of a directory tree like
+/// [`walk_dir_skip_error()`], logging the skipped errors.
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+pub async fn walk_dir_skip_error_and_log<P, L>(
+    path: P,
+    log_level: L,
+) -> std::io::Result<WalkDirStream>
+where
+    P: AsRef<std::path::Path>,
+    L: Into<crate::Level>,
+{
+    WalkDirStream::new(path.as_ref(), Skipper::with_log_level(log_level.into())).await
+}
+
+// What was found about an entry before yielding it
+enum Inspected {
+    Leaf,
+    Directory(tokio::fs::ReadDir, PathBuf),
+    // The type of the entry could not be read, so the entry is skipped
+    Failed(io::Error),
+    // The directory could not be opened, so only its content is skipped
+    DirectoryFailed(io::Error, PathBuf),
+}
+
+type Inspection = Pin<Box<dyn Future<Output = (tokio::fs::DirEntry, Inspected)> + Send>>;
+
+async fn inspect(entry: tokio::fs::DirEntry, descend: bool) -> (tokio::fs::DirEntry, Inspected) {
+    let inspected = match entry.file_type().await {
+        Ok(file_type) if descend && file_type.is_dir() => {
+            let path = entry.path();
+            match tokio::fs::read_dir(&path).await {
+                Ok(entries) => Inspected::Directory(entries, path),
+                Err(error) => Inspected::DirectoryFailed(error, path),
+            }
+        }
+        Ok(_) => Inspected::Leaf,
+        Err(error) => Inspected::Failed(error),
+    };
+    (entry, inspected)
+}
+
+/// A stream of the entries of a directory tree, skipping the errors.
+///
+/// This `struct` is created by [`walk_dir_skip_error()`].
+pub struct WalkDirStream {
+    // The directories being read, with their path and depth
+    stack: Vec<(tokio::fs::ReadDir, PathBuf, usize)>,
+    // The entry being inspected, with its depth
+    inspection: Option<(Inspection, usize)>,
+    max_depth: usize,
+    skipper: Skipper,
+}
+
+impl WalkDirStream {
+    skip_adapter_methods!();
+    budget_adapter_methods!();
+
+    async fn new(path: &std::path::Path, skipper: Skipper) -> io::Result<Self> {
+        let entries = tokio::fs::read_dir(path).await?;
+        Ok(WalkDirStream {
+            stack: vec![(entries, path.to_path_buf(), 1)],
+            inspection: None,
+            max_depth: usize::MAX,
+            skipper,
+        })
+    }
+
+    /// Descends at most `max_depth` levels below the directory: the entries
+    /// of the directory itself are at depth 1.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+impl Stream for WalkDirStream {
+    type Item = tokio::fs::DirEntry;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while !this.skipper.exhausted() {
+            if let Some((inspection, depth)) = this.inspection.as_mut() {
+                let depth = *depth;
+                let (entry, inspected) = ready!(inspection.as_mut().poll(cx));
+                this.inspection = None;
+                match inspected {
+                    Inspected::Leaf => {}
+                    Inspected::Directory(entries, path) => {
+                        this.stack.push((entries, path, depth + 1));
+                    }
+                    Inspected::Failed(error) => {
+                        this.skipper.skip(&PathError::new(&entry.path(), &error));
+                        continue;
+                    }
+                    Inspected::DirectoryFailed(error, path) => {
+                        this.skipper.skip(&PathError::new(&path, &error));
+                    }
+                }
+                this.skipper.ok();
+                return Poll::Ready(Some(entry));
+            }
+            let Some((entries, path, depth)) = this.stack.last_mut() else {
+                return Poll::Ready(None);
+            };
+            match ready!(entries.poll_next_entry(cx)) {
+                Ok(Some(entry)) => {
+                    let descend = *depth < this.max_depth;
+                    this.inspection = Some((Box::pin(inspect(entry, descend)), *depth));
+                }
+                Ok(None) => {
+                    this.stack.pop();
+                }
+                Err(error) => this.skipper.skip(&PathError::new(path, &error)),
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+/// A [`SkipSink`] forwarding the skipped errors into a
+/// [`tokio::sync::mpsc::Sender`](::tokio::sync::mpsc::Sender), so an
+/// asynchronous task can aggregate the failures of many pipelines.
+///
+/// The errors are sent with a non-blocking `try_send()`: when the channel is
+/// full or closed, the error is dropped and counted (see
+/// [`MpscSink::dropped()`]).
+///
+/// ```edition2018
+/// use skip_error::{MpscSink, SkipErrorStreamExt};
+/// use std::sync::atomic::Ordering;
+/// use tokio_stream::StreamExt;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let (sender, mut receiver) = tokio::sync::mpsc::channel(1);
+/// let sink = MpscSink::new(sender);
+/// let dropped = sink.dropped();
+/// let numbers: Vec<u32> = tokio_stream::iter(vec!["1", "two", "3", "four"])
+///   .map(|string_number| string_number.parse::<u32>())
+///   .skip_error()
+///   .with_sink(sink)
+///   .collect()
+///   .await;
+/// assert_eq!(numbers, vec![1, 3]);
+/// let skipped = receiver.recv().await.unwrap();
+/// assert_eq!(skipped.message(), "invalid digit found in string");
+/// assert_eq!(skipped.index(), Some(1));
+/// // The channel has a capacity of 1, so the second error has been dropped
+/// assert_eq!(dropped.load(Ordering::Relaxed), 1);
+/// # }
+/// ```
+pub struct MpscSink {
+    sender: Sender<OwnedSkippedError>,
+    dropped: Arc<AtomicUsize>,
+}
+
+impl MpscSink {
+    /// Creates a sink sending the skipped errors into `sender`.
+    pub fn new(sender: Sender<OwnedSkippedError>) -> Self {
+        MpscSink {
+            sender,
+            dropped: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Returns a shared counter of the errors which could not be sent,
+    /// because the channel was full or closed.
+    pub fn dropped(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.dropped)
+    }
+}
+
+impl SkipSink for MpscSink {
+    fn skipped(&mut self, skipped: &SkippedError<'_>) {
+        if self.sender.try_send(skipped.into()).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// A stream waiting for a delay after each error of the original stream,
+    /// before polling it again.
+    ///
+    /// This `struct` is created by [`SkipErrorStream::throttle_errors()`].
+    pub struct ThrottleErrors<S> {
+        #[pin]
+        inner: S,
+        delay: std::time::Duration,
+        sleep: Option<Pin<Box<Sleep>>>,
+    }
+}
+
+impl<S> ThrottleErrors<S> {
+    pub(crate) fn new(inner: S, delay: std::time::Duration) -> Self {
+        ThrottleErrors {
+            inner,
+            delay,
+            sleep: None,
+        }
+    }
+}
+
+impl<S, T, E> Stream for ThrottleErrors<S>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if let Some(sleep) = this.sleep.as_mut() {
+            ready!(sleep.as_mut().poll(cx));
+            *this.sleep = None;
+        }
+        let item = ready!(this.inner.poll_next(cx));
+        if let Some(Err(_)) = item {
+            *this.sleep = Some(Box::pin(tokio::time::sleep(*this.delay)));
+        }
+        Poll::Ready(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// A stream retrying a fallible asynchronous function on each item.
+    ///
+    /// This `struct` is created by [`RetryStreamExt::retry_transient()`].
+    pub struct RetryTransient<S, B, C, F, Fut>
+    where
+        S: Stream,
+    {
+        #[pin]
+        inner: S,
+        policy: RetryPolicy<B, C>,
+        f: F,
+        item: Option<S::Item>,
+        retry: u32,
+        #[pin]
+        future: Option<Fut>,
+        sleep: Option<Pin<Box<Sleep>>>,
+    }
+}
+
+impl<S, B, C, F, Fut, T, E> Stream for RetryTransient<S, B, C, F, Fut>
+where
+    S: Stream,
+    B: Backoff,
+    C: Classifier<E>,
+    F: FnMut(&S::Item) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if let Some(sleep) = this.sleep.as_mut() {
+                ready!(sleep.as_mut().poll(cx));
+                *this.sleep = None;
+                if let Some(item) = this.item.as_ref() {
+                    this.future.set(Some((this.f)(item)));
+                }
+            }
+            if let Some(future) = this.future.as_mut().as_pin_mut() {
+                let result = ready!(future.poll(cx));
+                this.future.set(None);
+                match result {
+                    Err(error)
+                        if *this.retry + 1 < this.policy.attempts
+                            && this.policy.classifier.is_transient(&error) =>
+                    {
+                        *this.retry += 1;
+                        let delay = this.policy.backoff.delay(*this.retry);
+                        *this.sleep = Some(Box::pin(tokio::time::sleep(delay)));
+                    }
+                    result => {
+                        *this.item = None;
+                        *this.retry = 0;
+                        return Poll::Ready(Some(result));
+                    }
+                }
+                continue;
+            }
+            match ready!(this.inner.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    this.future.set(Some((this.f)(&item)));
+                    *this.item = Some(item);
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = usize::from(self.item.is_some());
+        let (lower, upper) = self.inner.size_hint();
+        (
+            lower.saturating_add(pending),
+            upper.and_then(|upper| upper.checked_add(pending)),
+        )
+    }
+}
+
+/// Trait to extend any [`Stream`] with fallible asynchronous functions whose
+/// transient errors are retried, waiting with [`tokio::time::sleep()`]
+/// between the attempts.
+pub trait RetryStreamExt: Stream + Sized {
+    /// Applies `f` on each item, retrying the transient errors as decided by
+    /// `policy`. The stream yields the first `Ok()` value, the first error
+    /// which is not transient, or the last error for each item.
+    ///
+    /// This can be combined with
+    /// [`SkipErrorStreamExt::skip_error_and_log()`] to log the errors
+    /// remaining after all the attempts.
+    fn retry_transient<B, C, F, Fut, T, E>(
+        self,
+        policy: RetryPolicy<B, C>,
+        f: F,
+    ) -> RetryTransient<Self, B, C, F, Fut>
+    where
+        B: Backoff,
+        C: Classifier<E>,
+        F: FnMut(&Self::Item) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        RetryTransient {
+            inner: self,
+            policy,
+            f,
+            item: None,
+            retry: 0,
+            future: None,
+            sleep: None,
+        }
+    }
+
+    /// Applies `f` on each item like [`RetryStreamExt::retry_transient()`],
+    /// then skips the items which are still failing.
+    ///
+    /// ```edition2018
+    /// use skip_error::{ExponentialBackoff, RetryPolicy, RetryStreamExt};
+    /// use std::time::Duration;
+    /// use tokio_stream::StreamExt;
+    /// # #[tokio::main(flavor = "current_thread", start_paused = true)]
+    /// # async fn main() {
+    /// let policy = RetryPolicy::new(
+    ///   3,
+    ///   ExponentialBackoff::new(Duration::from_millis(100), Duration::from_secs(1)),
+    /// )
+    /// .with_classifier(|error: &String| error == "timeout");
+    /// let mut attempts = 0;
+    /// let start = tokio::time::Instant::now();
+    /// let numbers: Vec<u32> = tokio_stream::iter(vec!["1", "2", "three"])
+    ///   .retry_transient_then_skip(policy, |item| {
+    ///     attempts += 1;
+    ///     // "2" times out twice, "three" is never a number
+    ///     let result = match *item {
+    ///       "2" if attempts < 4 => Err("timeout".to_string()),
+    ///       item => item.parse::<u32>().map_err(|error| error.to_string()),
+    ///     };
+    ///     async move { result }
+    ///   })
+    ///   .collect()
+    ///   .await;
+    /// assert_eq!(numbers, vec![1, 2]);
+    /// assert_eq!(attempts, 5);
+    /// assert_eq!(start.elapsed(), Duration::from_millis(300));
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "forbid-silent",
+        deprecated(
+            note = "the errors must not be skipped silently, log them with `retry_transient(..).skip_error_and_log()`"
+        )
+    )]
+    #[allow(deprecated)]
+    fn retry_transient_then_skip<B, C, F, Fut, T, E>(
+        self,
+        policy: RetryPolicy<B, C>,
+        f: F,
+    ) -> SkipErrorStream<RetryTransient<Self, B, C, F, Fut>>
+    where
+        B: Backoff,
+        C: Classifier<E>,
+        F: FnMut(&Self::Item) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+    {
+        self.retry_transient(policy, f).skip_error()
+    }
+}
+
+impl<S> RetryStreamExt for S where S: Stream {}
+
+// Allows at most one log per interval, counting the suppressed ones.  The
+// clock of `tokio::time` is used, so it follows a paused runtime in tests.
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+pub(crate) struct RateLimiter {
+    interval: std::time::Duration,
+    last: Option<tokio::time::Instant>,
+    suppressed: usize,
+}
+
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+impl RateLimiter {
+    pub(crate) fn new(interval: std::time::Duration) -> Self {
+        RateLimiter {
+            interval,
+            last: None,
+            suppressed: 0,
+        }
+    }
+
+    // Returns the number of logs suppressed since the last one if a log is
+    // allowed now, or `None` if this log must be suppressed.
+    pub(crate) fn allow(&mut self) -> Option<usize> {
+        let now = tokio::time::Instant::now();
+        match self.last {
+            Some(last) if now.duration_since(last) < self.interval => {
+                self.suppressed += 1;
+                None
+            }
+            _ => {
+                self.last = Some(now);
+                Some(std::mem::take(&mut self.suppressed))
+            }
+        }
+    }
+}
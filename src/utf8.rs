@@ -0,0 +1,173 @@
+//! Lossy decoding of UTF-8, skipping the invalid sequences.
+use crate::Skipper;
+
+/// An invalid UTF-8 sequence, skipped by [`Utf8Skipping`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidUtf8 {
+    offset: usize,
+    len: usize,
+}
+
+impl InvalidUtf8 {
+    /// Position of the sequence in the decoded bytes.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Number of bytes of the sequence, which were dropped.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the sequence has no bytes, which never happens.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl core::fmt::Display for InvalidUtf8 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let unit = if self.len == 1 { "byte" } else { "bytes" };
+        write!(
+            f,
+            "invalid UTF-8 sequence of {} {} at offset {}",
+            self.len, unit, self.offset
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidUtf8 {}
+
+/// Returns an iterator over the valid UTF-8 slices of `bytes`, skipping the
+/// invalid sequences, like `String::from_utf8_lossy()` without the
+/// replacement characters.
+///
+/// Each slice is counted as an `Ok()` value and each invalid sequence as a
+/// skipped error in the [`SkipReport`](crate::SkipReport), and the number of
+/// dropped bytes is given by [`Utf8Skipping::dropped_bytes()`]. The characters
+/// are iterated with `.flat_map(str::chars)`.
+///
+/// ```edition2018
+/// let mut decoded = skip_error::decode_utf8_skipping(b"caf\xc3\xa9 \xff\xfeau lait\xe2\x82");
+/// let text: String = decoded.by_ref().collect();
+/// assert_eq!(text, "café au lait");
+/// assert_eq!(decoded.report().skipped(), 3);
+/// assert_eq!(decoded.dropped_bytes(), 4);
+///
+/// let chars: Vec<char> = skip_error::decode_utf8_skipping(b"a\xffb").flat_map(str::chars).collect();
+/// assert_eq!(chars, vec!['a', 'b']);
+/// ```
+#[cfg_attr(
+    feature = "forbid-silent",
+    deprecated(
+        note = "the errors must not be skipped silently, log them with `decode_utf8_skipping_and_log()`"
+    )
+)]
+pub fn decode_utf8_skipping(bytes: &[u8]) -> Utf8Skipping<'_> {
+    Utf8Skipping::new(bytes, Skipper::new())
+}
+
+/// Returns an iterator over the valid UTF-8 slices of `bytes`, logging and
+/// skipping the invalid sequences (see [`decode_utf8_skipping()`]).
+///
+/// ```edition2018
+/// # testing_logger::setup();
+/// let text: String = skip_error::decode_utf8_skipping_and_log(
+///   b"stop\xff 1\xe2\x82",
+#[cfg_attr(
+    all(feature = "log", not(feature = "tracing")),
+    doc = "  log::Level::Warn,"
+)]
+#[cfg_attr(feature = "tracing", doc = "  tracing::Level::WARN,")]
+/// )
+/// .collect();
+/// assert_eq!(text, "stop 1");
+/// testing_logger::validate(|captured_logs| {
+///   assert_eq!(captured_logs[0].body, "invalid UTF-8 sequence of 1 byte at offset 4");
+///   assert_eq!(captured_logs[1].body, "invalid UTF-8 sequence of 2 bytes at offset 7");
+/// });
+/// ```
+#[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+pub fn decode_utf8_skipping_and_log<L>(bytes: &[u8], log_level: L) -> Utf8Skipping<'_>
+where
+    L: Into<crate::Level>,
+{
+    Utf8Skipping::new(bytes, Skipper::with_log_level(log_level.into()))
+}
+
+/// An iterator over the valid UTF-8 slices of some bytes, skipping the
+/// invalid sequences.
+///
+/// This `struct` is created by [`decode_utf8_skipping()`].
+pub struct Utf8Skipping<'a> {
+    remaining: &'a [u8],
+    offset: usize,
+    dropped_bytes: usize,
+    skipper: Skipper,
+}
+
+impl<'a> Utf8Skipping<'a> {
+    skip_adapter_methods!();
+    budget_adapter_methods!();
+
+    fn new(bytes: &'a [u8], skipper: Skipper) -> Self {
+        Utf8Skipping {
+            remaining: bytes,
+            offset: 0,
+            dropped_bytes: 0,
+            skipper,
+        }
+    }
+
+    /// Number of bytes dropped with the invalid sequences so far.
+    pub fn dropped_bytes(&self) -> usize {
+        self.dropped_bytes
+    }
+
+    fn advance(&mut self, len: usize) -> &'a [u8] {
+        let (consumed, remaining) = self.remaining.split_at(len);
+        self.remaining = remaining;
+        self.offset += len;
+        consumed
+    }
+}
+
+impl<'a> Iterator for Utf8Skipping<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.skipper.exhausted() && !self.remaining.is_empty() {
+            let (valid_up_to, invalid_len) = match core::str::from_utf8(self.remaining) {
+                Ok(_) => (self.remaining.len(), 0),
+                // An incomplete sequence at the end drops all the rest
+                Err(error) => {
+                    let valid_up_to = error.valid_up_to();
+                    let invalid_len = error
+                        .error_len()
+                        .unwrap_or(self.remaining.len() - valid_up_to);
+                    (valid_up_to, invalid_len)
+                }
+            };
+            if valid_up_to > 0 {
+                let valid = self.advance(valid_up_to);
+                self.skipper.ok();
+                // SAFETY: the bytes before `valid_up_to` were validated by
+                // `from_utf8()`
+                return Some(unsafe { core::str::from_utf8_unchecked(valid) });
+            }
+            let error = InvalidUtf8 {
+                offset: self.offset,
+                len: invalid_len,
+            };
+            self.advance(invalid_len);
+            self.dropped_bytes += invalid_len;
+            self.skipper.skip(&error);
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining.len()))
+    }
+}
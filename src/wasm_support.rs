@@ -0,0 +1,52 @@
+//! Emitting the skipped errors to the console of the browser.
+use crate::{SkipSink, SkippedError};
+use web_sys::{console, wasm_bindgen::JsValue};
+
+/// A [`SkipSink`] emitting the skipped errors to the console of the browser
+/// (or of any WASM host providing one), with `console.warn` or
+/// `console.error`.
+///
+/// Each message is `item <index>: <error>`, or only the error if the index is
+/// unknown, like with [`TextSink`](crate::TextSink).
+///
+/// ```edition2018,no_run
+/// use skip_error::{ConsoleSink, SkipError};
+/// let numbers: Vec<u32> = ["1", "two", "3", "four"]
+///   .iter()
+///   .map(|string_number| string_number.parse())
+///   .skip_error()
+///   .with_sink(ConsoleSink::warn())
+///   .collect();
+/// assert_eq!(numbers, vec![1, 3]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ConsoleSink {
+    error: bool,
+}
+
+impl ConsoleSink {
+    /// Creates a sink emitting with `console.warn`.
+    pub fn warn() -> Self {
+        ConsoleSink { error: false }
+    }
+
+    /// Creates a sink emitting with `console.error`.
+    pub fn error() -> Self {
+        ConsoleSink { error: true }
+    }
+}
+
+impl SkipSink for ConsoleSink {
+    fn skipped(&mut self, skipped: &SkippedError<'_>) {
+        let message = match skipped.index() {
+            Some(index) => format!("item {}: {}", index, skipped.error()),
+            None => skipped.error().to_string(),
+        };
+        let message = JsValue::from_str(&message);
+        if self.error {
+            console::error_1(&message);
+        } else {
+            console::warn_1(&message);
+        }
+    }
+}